@@ -0,0 +1,6 @@
+# builds `dess_fmi.fmu` for linux64 in the `target` folder of the workspace
+cargo build --release -p dess-examples-fmi && \
+rm -rf ../target/fmu && mkdir -p ../target/fmu/binaries/linux64 && \
+cp ../target/release/libdess_fmi.so ../target/fmu/binaries/linux64/dess_fmi.so && \
+cargo run --release -p dess-examples-fmi --bin write_model_description -- ../target/fmu && \
+(cd ../target/fmu && zip -r ../dess_fmi.fmu modelDescription.xml binaries)
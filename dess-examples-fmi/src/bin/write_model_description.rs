@@ -0,0 +1,10 @@
+/// Writes `modelDescription.xml` to the directory given as first argument,
+/// defaulting to the current directory
+fn main() -> anyhow::Result<()> {
+    let dir = std::env::args().nth(1).unwrap_or_else(|| ".".into());
+    std::fs::write(
+        std::path::Path::new(&dir).join("modelDescription.xml"),
+        dess_fmi::model_description(),
+    )?;
+    Ok(())
+}
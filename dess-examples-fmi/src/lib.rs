@@ -0,0 +1,120 @@
+use dess_examples::three_thermal_mass_sys::System3TM;
+
+/// must match the name of the shared library in the FMU's `binaries` folder
+pub const MODEL_IDENTIFIER: &str = "dess_fmi";
+/// must change whenever the variables of the exported system change
+pub const GUID: &str = "{5d3f8c2e-7a41-4c0b-9e2d-6b1f0a9c8e47}";
+
+dess::export_fmi2!(System3TM, model_identifier: MODEL_IDENTIFIER, guid: GUID);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dess::fmi::{FmiModel, FMI2_COSIMULATION, FMI2_ERROR, FMI2_OK};
+    use std::ffi::CString;
+
+    /// calls the exported functions in the order an FMI master would
+    #[test]
+    fn test_stub_master() {
+        unsafe {
+            let name = CString::new("sys").unwrap();
+            let guid = CString::new(GUID).unwrap();
+            let wrong_guid = CString::new("{}").unwrap();
+            assert!(fmi2Instantiate(
+                name.as_ptr(),
+                FMI2_COSIMULATION,
+                wrong_guid.as_ptr(),
+                std::ptr::null(),
+                std::ptr::null(),
+                0,
+                0
+            )
+            .is_null());
+            let c = fmi2Instantiate(
+                name.as_ptr(),
+                FMI2_COSIMULATION,
+                guid.as_ptr(),
+                std::ptr::null(),
+                std::ptr::null(),
+                0,
+                0,
+            );
+            assert!(!c.is_null());
+
+            let sys = System3TM::default();
+            let vars = sys.fmi_variables();
+            let vr_of = |name: &str| vars.iter().position(|v| v.name == name).unwrap() as u32;
+
+            assert_eq!(fmi2SetupExperiment(c, 0, 0.0, 0.0, 1, 1.0), FMI2_OK);
+            assert_eq!(fmi2EnterInitializationMode(c), FMI2_OK);
+            // double capacitance of `m1` and set initial state of `m3`
+            let vrs = [vr_of("m1.c"), vr_of("m3.state")];
+            assert_eq!(
+                fmi2SetReal(c, vrs.as_ptr(), 2, [2.0, 20.0].as_ptr()),
+                FMI2_OK
+            );
+            // outputs are read only
+            assert_eq!(
+                fmi2SetReal(c, [vr_of("h12.q")].as_ptr(), 1, [0.0].as_ptr()),
+                FMI2_ERROR
+            );
+            // cannot step before leaving initialization mode
+            assert_eq!(fmi2DoStep(c, 0.0, 0.1, 1), FMI2_ERROR);
+            assert_eq!(fmi2ExitInitializationMode(c), FMI2_OK);
+            // states are `local`, so only parameters can be set from here on
+            assert_eq!(
+                fmi2SetReal(c, [vr_of("m3.state")].as_ptr(), 1, [0.0].as_ptr()),
+                FMI2_ERROR
+            );
+            assert_eq!(
+                fmi2SetReal(c, [vr_of("m1.c")].as_ptr(), 1, [2.0].as_ptr()),
+                FMI2_OK
+            );
+
+            let h = 0.1;
+            for k in 0..10 {
+                assert_eq!(fmi2DoStep(c, k as f64 * h, h, 1), FMI2_OK);
+            }
+            // communication point must match the model time
+            assert_eq!(fmi2DoStep(c, 0.5, h, 1), FMI2_ERROR);
+            assert_eq!(fmi2DoStep(c, 1.0, -h, 1), FMI2_ERROR);
+            // only the current state is kept
+            let inst = dess::fmi::instance::<System3TM>(c).unwrap();
+            assert_eq!(inst.model.history.len(), 1);
+            assert_eq!(inst.model.m1.history.len(), 1);
+
+            let vrs = [vr_of("time"), vr_of("m1.temp"), vr_of("m3.state")];
+            let mut values = [0.0; 3];
+            assert_eq!(
+                fmi2GetReal(c, vrs.as_ptr(), 3, values.as_mut_ptr()),
+                FMI2_OK
+            );
+
+            // same result as stepping the system directly
+            let mut sys = System3TM::default();
+            sys.m1.c = 2.0;
+            sys.m3.state.temp = 20.0;
+            for k in 0..10 {
+                sys.advance_to(k as f64 * h + h);
+            }
+            assert!((values[0] - 1.0).abs() < 1e-12);
+            assert_eq!(values[1], sys.m1.state.temp);
+            assert_eq!(values[2], sys.m3.state.temp);
+
+            assert_eq!(fmi2Terminate(c), FMI2_OK);
+            fmi2FreeInstance(c);
+        }
+    }
+
+    #[test]
+    fn test_model_description() {
+        let xml = model_description();
+        assert!(xml.contains(&format!("guid=\"{}\"", GUID)));
+        assert!(xml.contains("<CoSimulation modelIdentifier=\"dess_fmi\""));
+        assert!(xml.contains(
+            "<ScalarVariable name=\"h12.h\" valueReference=\"7\" causality=\"parameter\""
+        ));
+        assert!(xml.contains("<Real start=\"5\"/>"));
+        assert!(xml.contains("name=\"m3.dtemp\""));
+    }
+}
@@ -1,5 +1,14 @@
 use dess::prelude::*;
+use dess_examples::building_sys::{Building, Room};
 use dess_examples::components::*;
+use dess_examples::coolant_loop_sys::CoolantLoop;
+use dess_examples::cooling_plate_sys::CoolingPlate;
+use dess_examples::discretized_wall_sys::DiscretizedWall;
+use dess_examples::heated_mass_sys::HeatedMass;
+use dess_examples::heated_room_sys::HeatedRoom;
+use dess_examples::mass_spring_damper_sys::MassSpringDamper;
+use dess_examples::rc_ladder_sys::RCLadder;
+use dess_examples::rlc_sys::ParallelRLC;
 use dess_examples::three_thermal_mass_sys::*;
 use dess_examples::three_thrml_mass_w_bc_sys::System3TMWithBC;
 
@@ -20,5 +29,60 @@ fn dess_pyo3(_py: Python, m: &PyModule) -> PyResult<()> {
     m.add_class::<Conductance>()?;
     m.add_class::<ConductanceState>()?;
     m.add_class::<ConductanceStateHistoryVec>()?;
+    m.add_class::<RCLadder>()?;
+    m.add_class::<Capacitor>()?;
+    m.add_class::<VoltageSource>()?;
+    m.add_class::<CapacitorState>()?;
+    m.add_class::<CapacitorStateHistoryVec>()?;
+    m.add_class::<Resistor>()?;
+    m.add_class::<CurrentSource>()?;
+    m.add_class::<ResistorState>()?;
+    m.add_class::<ResistorStateHistoryVec>()?;
+    m.add_class::<ParallelRLC>()?;
+    m.add_class::<Inductor>()?;
+    m.add_class::<InductorState>()?;
+    m.add_class::<InductorStateHistoryVec>()?;
+    m.add_class::<CoolantLoop>()?;
+    m.add_class::<Tank>()?;
+    m.add_class::<PressureReservoir>()?;
+    m.add_class::<TankState>()?;
+    m.add_class::<TankStateHistoryVec>()?;
+    m.add_class::<Pipe>()?;
+    m.add_class::<Pump>()?;
+    m.add_class::<PipeState>()?;
+    m.add_class::<PipeStateHistoryVec>()?;
+    m.add_class::<MassSpringDamper>()?;
+    m.add_class::<Mass>()?;
+    m.add_class::<FixedGround>()?;
+    m.add_class::<MassState>()?;
+    m.add_class::<MassStateHistoryVec>()?;
+    m.add_class::<Inertia>()?;
+    m.add_class::<InertiaState>()?;
+    m.add_class::<InertiaStateHistoryVec>()?;
+    m.add_class::<Spring>()?;
+    m.add_class::<SpringState>()?;
+    m.add_class::<SpringStateHistoryVec>()?;
+    m.add_class::<TorsionSpring>()?;
+    m.add_class::<TorsionSpringState>()?;
+    m.add_class::<TorsionSpringStateHistoryVec>()?;
+    m.add_class::<Damper>()?;
+    m.add_class::<DamperState>()?;
+    m.add_class::<DamperStateHistoryVec>()?;
+    m.add_class::<CoolingPlate>()?;
+    m.add_class::<RadiativeConductance>()?;
+    m.add_class::<ConvectiveConductance>()?;
+    m.add_class::<VariableThermalMass>()?;
+    m.add_class::<HeatedMass>()?;
+    m.add_class::<FlowSource>()?;
+    m.add_class::<SourceState>()?;
+    m.add_class::<SourceStateHistoryVec>()?;
+    m.add_class::<HeatedRoom>()?;
+    m.add_class::<LayeredWall>()?;
+    m.add_class::<LayeredWallState>()?;
+    m.add_class::<LayeredWallStateHistoryVec>()?;
+    m.add_class::<DiscretizedWall>()?;
+    m.add_class::<Building>()?;
+    m.add_class::<Room>()?;
+    m.add_class::<Network>()?;
     Ok(())
 }
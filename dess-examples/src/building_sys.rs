@@ -0,0 +1,245 @@
+use crate::components::*;
+use crate::imports::*;
+
+/// Room air `air` heated by `heater` and losing heat through `wall` to the
+/// outdoors `outdoors`, usable on its own or as a [Subsystem] of a [Building]
+#[pyo3_api(
+    #[new]
+    fn __new__(
+        solver_type: String,
+        air: ThermalMass,
+        heater: HeatSource,
+        wall: Conductance,
+        outdoors: ThermalReservoir,
+        t_report: Vec<f64>,
+    ) -> Self {
+        Self {
+            solver_type: SolverTypes::from_json(&solver_type).unwrap(),
+            air,
+            heater,
+            wall,
+            outdoors,
+            t_report,
+            state: Default::default(),
+            history: Default::default(),
+        }
+    }
+
+    #[getter]
+    fn get_solver_type(&self) -> String {
+        self.solver_type.to_json()
+    }
+
+    #[pyo3(name = "walk")]
+    fn walk_py(&mut self) {
+        self.walk();
+    }
+)]
+#[solver(
+    /// Updates time derivatives of states.
+    /// This method must be user defined in `solver` macro args.
+    fn update_derivs(&mut self) {
+        self.reset_derivs();
+        connect_states!(self, (air, outdoors, wall), (air, heater));
+        update_derivs!(self, (air, outdoors, wall), (air, heater));
+    }
+)]
+#[derive(HistoryMethods, BareClone)]
+#[common_derives]
+pub struct Room {
+    #[skip_get]
+    pub solver_type: SolverTypes,
+    // components
+    #[use_state]
+    pub air: ThermalMass,
+    #[save_state]
+    #[breakpoints]
+    pub heater: HeatSource,
+    /// wall connects air to outdoors
+    #[save_state]
+    pub wall: Conductance,
+    #[use_state]
+    pub outdoors: ThermalReservoir,
+    // fields needed by `solver` procedural macro
+    pub t_report: Vec<f64>,
+    pub state: SystemState,
+    pub history: SystemStateHistoryVec,
+}
+
+impl Default for Room {
+    fn default() -> Self {
+        Self {
+            solver_type: SolverTypes::RK45CashKarp(Box::default()),
+            air: ThermalMass {
+                c: 1e5,
+                state: ThermalMassState {
+                    temp: 15.0,
+                    dtemp: Default::default(),
+                },
+                history: Default::default(),
+            },
+            heater: HeatSource::constant(500.0),
+            wall: Conductance {
+                h: 50.0,
+                ..Default::default()
+            },
+            outdoors: ThermalReservoir::default(),
+            t_report: Vec::linspace(0.0, 86400.0, 49),
+            state: Default::default(),
+            history: Default::default(),
+        }
+    }
+}
+
+/// Two [Room]s exchanging heat through `partition` between their air
+#[pyo3_api(
+    #[new]
+    fn __new__(
+        solver_type: String,
+        room1: Room,
+        room2: Room,
+        partition: Conductance,
+        t_report: Vec<f64>,
+    ) -> Self {
+        Self {
+            solver_type: SolverTypes::from_json(&solver_type).unwrap(),
+            room1: room1.into(),
+            room2: room2.into(),
+            partition,
+            t_report,
+            state: Default::default(),
+            history: Default::default(),
+        }
+    }
+
+    #[getter]
+    fn get_solver_type(&self) -> String {
+        self.solver_type.to_json()
+    }
+
+    #[pyo3(name = "walk")]
+    fn walk_py(&mut self) {
+        self.walk();
+    }
+)]
+#[solver(
+    /// Updates time derivatives of states.
+    /// This method must be user defined in `solver` macro args.
+    fn update_derivs(&mut self) {
+        self.reset_derivs();
+        self.room1.update_derivs_at(self.state.time);
+        self.room2.update_derivs_at(self.state.time);
+        connect_states!(self, (room1.air, room2.air, partition));
+        update_derivs!(self, (room1.air, room2.air, partition));
+    }
+)]
+#[derive(HistoryMethods, BareClone)]
+#[common_derives]
+pub struct Building {
+    #[skip_get]
+    pub solver_type: SolverTypes,
+    // subsystems
+    #[use_state]
+    #[breakpoints]
+    pub room1: Subsystem<Room>,
+    #[use_state]
+    #[breakpoints]
+    pub room2: Subsystem<Room>,
+    /// partition connects air of room1 to air of room2
+    #[save_state]
+    pub partition: Conductance,
+    // fields needed by `solver` procedural macro
+    pub t_report: Vec<f64>,
+    pub state: SystemState,
+    pub history: SystemStateHistoryVec,
+}
+
+impl Default for Building {
+    fn default() -> Self {
+        Self {
+            solver_type: SolverTypes::RK45CashKarp(Box::default()),
+            // heater switched on between report times
+            room1: Room {
+                heater: HeatSource::signal(SignalTypes::Step(Step {
+                    time: 4000.0,
+                    before: 0.0,
+                    after: 1000.0,
+                })),
+                ..Default::default()
+            }
+            .into(),
+            room2: Room::default().into(),
+            partition: Conductance {
+                h: 20.0,
+                ..Default::default()
+            },
+            t_report: Vec::linspace(0.0, 172800.0, 97),
+            state: Default::default(),
+            history: Default::default(),
+        }
+    }
+}
+
+impl Building {
+    /// Returns steady-state air temperatures `[room1, room2]` with heaters
+    /// at their current output
+    pub fn steady_temps(&self) -> [f64; 2] {
+        let (r1, r2) = (&self.room1, &self.room2);
+        let hp = self.partition.h;
+        // heat balance on each room is linear in both temperatures
+        let a = [[r1.wall.h + hp, -hp], [-hp, r2.wall.h + hp]];
+        let b = [
+            r1.heater.flow() + r1.wall.h * r1.outdoors.state.temp,
+            r2.heater.flow() + r2.wall.h * r2.outdoors.state.temp,
+        ];
+        let det = a[0][0] * a[1][1] - a[0][1] * a[1][0];
+        [
+            (b[0] * a[1][1] - a[0][1] * b[1]) / det,
+            (a[0][0] * b[1] - b[0] * a[1][0]) / det,
+        ]
+    }
+}
+
+pub fn run_building() {
+    let mut sys = Building::default();
+    let t_elapsed = time_it!(sys.walk());
+    println!("Building elapsed time: {} μs", t_elapsed.as_micros());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_building_steady_state() {
+        let mut sys = Building::default();
+        assert_eq!(
+            sys.state_names(),
+            vec!["room1.air", "room1.outdoors", "room2.air", "room2.outdoors"]
+        );
+        sys.walk();
+        let [temp1, temp2] = sys.steady_temps();
+        assert!((sys.room1.air.state.temp - temp1).abs() < 1e-3);
+        assert!((sys.room2.air.state.temp - temp2).abs() < 1e-3);
+        assert!(temp1 > temp2);
+        // subsystem keeps time with the parent, so its heater switches on
+        // between the second and third report times
+        assert_eq!(sys.room1.history.time, sys.history.time);
+        assert_eq!(sys.room1.heater.history.flow[2], 0.0);
+        assert_eq!(sys.room1.heater.history.flow[3], 1000.0);
+        let columns = sys.history_columns();
+        assert_eq!(
+            columns.get("room1.air.temp").unwrap(),
+            &sys.room1.air.history.temp[..]
+        );
+        assert!(columns.get("partition.q").is_some());
+    }
+
+    #[test]
+    fn test_room_alone() {
+        let mut room = Room::default();
+        room.walk();
+        let temp_steady = room.heater.flow() / room.wall.h;
+        assert!((room.air.state.temp - temp_steady).abs() < 1e-3);
+    }
+}
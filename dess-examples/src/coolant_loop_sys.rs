@@ -0,0 +1,200 @@
+use crate::components::*;
+use crate::imports::*;
+
+/// Coolant loop: pump draws from an expansion tank `res` into tank `t1`,
+/// which drains through pipe `p12` into tank `t2` and back to `res` through
+/// pipe `p2r`
+#[pyo3_api(
+    #[new]
+    #[allow(clippy::too_many_arguments)]
+    fn __new__(
+        solver_type: String,
+        res: PressureReservoir,
+        pump: Pump,
+        t1: Tank,
+        p12: Pipe,
+        t2: Tank,
+        p2r: Pipe,
+        t_report: Vec<f64>,
+    ) -> Self {
+        Self {
+            solver_type: SolverTypes::from_json(&solver_type).unwrap(),
+            res,
+            pump,
+            t1,
+            p12,
+            t2,
+            p2r,
+            t_report,
+            state: Default::default(),
+            history: Default::default(),
+        }
+    }
+
+    #[getter]
+    fn get_solver_type(&self) -> String {
+        self.solver_type.to_json()
+    }
+
+    #[pyo3(name = "walk")]
+    fn walk_py(&mut self) {
+        self.walk();
+    }
+)]
+#[solver(
+    /// Updates time derivatives of states.
+    /// This method must be user defined in `solver` macro args.
+    fn update_derivs(&mut self) {
+        self.reset_derivs();
+        connect_states!(self, (res, t1, pump), (t1, t2, p12), (t2, res, p2r));
+        update_derivs!(self, (res, t1, pump), (t1, t2, p12), (t2, res, p2r));
+    }
+)]
+#[derive(HistoryMethods, BareClone)]
+#[common_derives]
+pub struct CoolantLoop {
+    #[skip_get]
+    pub solver_type: SolverTypes,
+    // components
+    #[use_state]
+    pub res: PressureReservoir,
+    /// pump drives flow from res to t1
+    #[save_state]
+    pub pump: Pump,
+    #[use_state]
+    pub t1: Tank,
+    #[save_state]
+    pub p12: Pipe,
+    #[use_state]
+    pub t2: Tank,
+    #[save_state]
+    pub p2r: Pipe,
+    // fields needed by `solver` procedural macro
+    pub t_report: Vec<f64>,
+    pub state: SystemState,
+    pub history: SystemStateHistoryVec,
+}
+
+impl Default for CoolantLoop {
+    fn default() -> Self {
+        let tank = |p0| Tank {
+            compliance: 1e-9,
+            state: TankState {
+                p: p0,
+                dp: Default::default(),
+            },
+            history: Default::default(),
+        };
+        // 2 m of 1/2 inch tubing carrying water
+        let pipe = Pipe::from_geometry(2.0, 0.0127, 1000., 1e-3, 0.03);
+        Self {
+            solver_type: SolverTypes::RK45CashKarp(Box::default()),
+            res: PressureReservoir {
+                state: TankState {
+                    p: 1e5,
+                    dp: Default::default(),
+                },
+                history: Default::default(),
+            },
+            pump: Pump {
+                shutoff_pressure: 2e5,
+                max_flow: 1e-3,
+                ..Default::default()
+            },
+            t1: tank(1e5),
+            p12: pipe.clone(),
+            t2: tank(1e5),
+            p2r: pipe,
+            t_report: Vec::linspace(0.0, 2.0, 21),
+            state: Default::default(),
+            history: Default::default(),
+        }
+    }
+}
+
+impl CoolantLoop {
+    /// Returns steady-state flow rate \[m³/s\], where the pump pressure rise
+    /// equals the pressure drop in both pipes
+    pub fn steady_flow(&self) -> f64 {
+        // pressure rise and drops are quadratic in flow
+        let a = self.pump.shutoff_pressure / self.pump.max_flow.powi(2)
+            + self.p12.k_turbulent
+            + self.p2r.k_turbulent;
+        let b = self.p12.r_laminar + self.p2r.r_laminar;
+        let c = -self.pump.shutoff_pressure * self.pump.speed.powi(2);
+        (-b + (b * b - 4. * a * c).sqrt()) / (2. * a)
+    }
+}
+
+pub fn run_coolant_loop() {
+    let mut sys = CoolantLoop::default();
+    let t_elapsed = time_it!(sys.walk());
+    println!("Coolant loop elapsed time: {} μs", t_elapsed.as_micros());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_coolant_loop_steady_state() {
+        let mut sys = CoolantLoop::default();
+        let q_steady = sys.steady_flow();
+        sys.walk();
+        for q in [sys.pump.state.q, sys.p12.state.q, sys.p2r.state.q] {
+            assert!((q - q_steady).abs() / q_steady < 1e-4);
+        }
+        let rise = sys.pump.pressure_rise(q_steady);
+        assert!((sys.t1.state.p - sys.res.state.p - rise).abs() / rise < 1e-3);
+        let drop = sys.p12.pressure_drop(q_steady);
+        assert!((sys.t1.state.p - sys.t2.state.p - drop).abs() / drop < 1e-3);
+        assert!(sys.t1.volume() > sys.t2.volume());
+    }
+
+    #[test]
+    fn test_pipe_flow_inverts_pressure_drop() {
+        let pipe = Pipe::from_geometry(2.0, 0.0127, 1000., 1e-3, 0.03);
+        // purely turbulent pipe, including at zero flow
+        let turbulent = Pipe {
+            r_laminar: 0.0,
+            ..pipe.clone()
+        };
+        for pipe in [pipe, turbulent] {
+            for q in [-2e-4, 0.0, 1e-6, 5e-4] {
+                let mut pipe = pipe.clone();
+                let p1 = PressureReservoir::default();
+                let mut p0 = PressureReservoir::default();
+                p0.state.p = pipe.pressure_drop(q);
+                pipe.set_flow(&p0, &p1);
+                assert!((pipe.state.q - q).abs() < 1e-12);
+            }
+        }
+        // pump with closed outlet at shutoff delivers nothing
+        let mut pump = Pump {
+            shutoff_pressure: 2e5,
+            max_flow: 1e-3,
+            ..Default::default()
+        };
+        let mut outlet = PressureReservoir::default();
+        outlet.state.p = 3e5;
+        pump.set_flow(&PressureReservoir::default(), &outlet);
+        assert_eq!(pump.state.q, 0.0);
+        // degenerate pumps deliver nothing rather than infinite or NaN flow
+        let mut inlet = PressureReservoir::default();
+        inlet.state.p = 1e5;
+        for (shutoff_pressure, max_flow) in [(0.0, 1e-3), (-2e5, 1e-3), (2e5, 0.0), (2e5, -1e-3)] {
+            let mut pump = Pump {
+                shutoff_pressure,
+                max_flow,
+                ..Default::default()
+            };
+            pump.set_flow(&inlet, &PressureReservoir::default());
+            assert_eq!(pump.state.q, 0.0);
+        }
+        let pump = Pump {
+            shutoff_pressure: 2e5,
+            ..Default::default()
+        };
+        assert_eq!(pump.pressure_rise(0.0), 2e5);
+    }
+}
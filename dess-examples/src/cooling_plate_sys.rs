@@ -0,0 +1,190 @@
+use crate::components::*;
+use crate::imports::*;
+
+/// Hot vertical plate `plate` cooling to surroundings `amb` by radiation
+/// `rad` and natural convection `conv`, with capacitance that rises with
+/// temperature
+#[pyo3_api(
+    #[new]
+    fn __new__(
+        solver_type: String,
+        plate: VariableThermalMass,
+        amb: ThermalReservoir,
+        rad: RadiativeConductance,
+        conv: ConvectiveConductance,
+        t_report: Vec<f64>,
+    ) -> Self {
+        Self {
+            solver_type: SolverTypes::from_json(&solver_type).unwrap(),
+            plate,
+            amb,
+            rad,
+            conv,
+            t_report,
+            state: Default::default(),
+            history: Default::default(),
+        }
+    }
+
+    #[getter]
+    fn get_solver_type(&self) -> String {
+        self.solver_type.to_json()
+    }
+
+    #[pyo3(name = "walk")]
+    fn walk_py(&mut self) {
+        self.walk();
+    }
+)]
+#[solver(
+    /// Updates time derivatives of states.
+    /// This method must be user defined in `solver` macro args.
+    fn update_derivs(&mut self) {
+        self.reset_derivs();
+        connect_states!(self, (plate, amb, rad), (plate, amb, conv));
+        update_derivs!(self, (plate, amb, rad), (plate, amb, conv));
+    }
+)]
+#[derive(HistoryMethods, BareClone)]
+#[common_derives]
+pub struct CoolingPlate {
+    #[skip_get]
+    pub solver_type: SolverTypes,
+    // components
+    #[use_state]
+    pub plate: VariableThermalMass,
+    #[use_state]
+    pub amb: ThermalReservoir,
+    #[save_state]
+    pub rad: RadiativeConductance,
+    #[save_state]
+    pub conv: ConvectiveConductance,
+    // fields needed by `solver` procedural macro
+    pub t_report: Vec<f64>,
+    pub state: SystemState,
+    pub history: SystemStateHistoryVec,
+}
+
+impl Default for CoolingPlate {
+    fn default() -> Self {
+        Self {
+            solver_type: SolverTypes::RK45CashKarp(Box::default()),
+            // 0.1 m square, 1 mm thick steel plate
+            plate: VariableThermalMass::new(vec![0.0, 500.0], vec![35.0, 45.0], 400.0).unwrap(),
+            amb: ThermalReservoir {
+                state: ThermalMassState {
+                    temp: 20.0,
+                    dtemp: Default::default(),
+                },
+                history: Default::default(),
+            },
+            // both faces
+            rad: RadiativeConductance {
+                emissivity: 0.8,
+                area: 0.02,
+                ..Default::default()
+            },
+            // air at roughly 300 K
+            conv: ConvectiveConductance {
+                area: 0.02,
+                length: 0.1,
+                k_fluid: 0.026,
+                correlation: NusseltCorrelation::ChurchillChu {
+                    pr: 0.71,
+                    beta: 1. / 300.,
+                    nu_kin: 1.6e-5,
+                    alpha: 2.2e-5,
+                },
+                ..Default::default()
+            },
+            t_report: Vec::linspace(0.0, 3600.0, 61),
+            state: Default::default(),
+            history: Default::default(),
+        }
+    }
+}
+
+pub fn run_cooling_plate() {
+    let mut sys = CoolingPlate::default();
+    let t_elapsed = time_it!(sys.walk());
+    println!("Cooling plate elapsed time: {} μs", t_elapsed.as_micros());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cooling_plate() {
+        let mut sys = CoolingPlate::default();
+        sys.walk();
+        let temps = &sys.plate.history.temp;
+        assert!(temps.windows(2).all(|t| t[1] < t[0]));
+        assert!(*temps.last().unwrap() > sys.amb.state.temp);
+        assert!(*temps.last().unwrap() - sys.amb.state.temp < 1e-2);
+        // radiation dominates while hot
+        assert!(sys.rad.history.q[1] > sys.conv.history.q[1]);
+        assert_eq!(
+            sys.plate.storage(),
+            35.0 + 10.0 * sys.plate.state.temp / 500.0
+        );
+
+        assert!(VariableThermalMass::default().validate().is_ok());
+        assert!(VariableThermalMass::new(vec![], vec![], 0.0).is_err());
+        assert!(VariableThermalMass::new(vec![0.0, 500.0], vec![35.0], 0.0).is_err());
+        assert!(VariableThermalMass::new(vec![500.0, 0.0], vec![35.0, 45.0], 0.0).is_err());
+
+        // tables are validated when deserialized, also inside a system
+        let mut bad = CoolingPlate::default();
+        bad.plate.temps = vec![500.0, 0.0];
+        let err = CoolingPlate::from_yaml(&bad.to_yaml()).unwrap_err();
+        assert!(
+            err.to_string().contains("must be strictly increasing"),
+            "{}",
+            err
+        );
+        let mut bad = VariableThermalMass::default();
+        bad.c.push(2.0);
+        assert!(VariableThermalMass::from_yaml(&bad.to_yaml()).is_err());
+        let good = CoolingPlate::default();
+        assert_eq!(CoolingPlate::from_yaml(&good.to_yaml()).unwrap(), good);
+    }
+
+    #[test]
+    fn test_nonlinear_conductances() {
+        let hot = ThermalReservoir {
+            state: ThermalMassState {
+                temp: 100.0,
+                dtemp: 0.0,
+            },
+            ..Default::default()
+        };
+        let cold = ThermalReservoir::default();
+        let mut rad = RadiativeConductance {
+            emissivity: 1.0,
+            area: 1.0,
+            ..Default::default()
+        };
+        rad.set_flow(&hot, &cold);
+        let q_celsius = rad.flow();
+        assert!(
+            (q_celsius - STEFAN_BOLTZMANN * (373.15f64.powi(4) - 273.15f64.powi(4))).abs() < 1e-9
+        );
+        rad.units = TemperatureUnits::Kelvin;
+        rad.set_flow(&hot, &cold);
+        assert!((rad.flow() - STEFAN_BOLTZMANN * 100f64.powi(4)).abs() < 1e-9);
+
+        let mut conv = ConvectiveConductance {
+            area: 2.0,
+            length: 0.05,
+            k_fluid: 0.6,
+            correlation: NusseltCorrelation::DittusBoelter { re: 1e4, pr: 7.0 },
+            ..Default::default()
+        };
+        conv.set_flow(&hot, &cold);
+        let h = 0.023 * 1e4f64.powf(0.8) * 7f64.powf(0.4) * 0.6 / 0.05;
+        assert!((conv.flow() - h * 2.0 * 100.0).abs() < 1e-6);
+        conv.set_flow(&cold, &hot);
+        assert!((conv.flow() + h * 2.0 * 100.0).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,199 @@
+use crate::components::*;
+use crate::imports::*;
+use std::f64::consts::PI;
+
+/// Wall discretized through its thickness into `nodes`, joined by `links`,
+/// between hot and cold reservoirs it exchanges heat with through `faces`
+#[pyo3_api(
+    #[new]
+    fn __new__(
+        solver_type: String,
+        hot: ThermalReservoir,
+        nodes: Vec<ThermalMass>,
+        links: Vec<Conductance>,
+        faces: [Conductance; 2],
+        cold: ThermalReservoir,
+        t_report: Vec<f64>,
+    ) -> Self {
+        Self {
+            solver_type: SolverTypes::from_json(&solver_type).unwrap(),
+            hot,
+            nodes,
+            links,
+            faces,
+            cold,
+            t_report,
+            state: Default::default(),
+            history: Default::default(),
+        }
+    }
+
+    #[getter]
+    fn get_solver_type(&self) -> String {
+        self.solver_type.to_json()
+    }
+
+    #[pyo3(name = "walk")]
+    fn walk_py(&mut self) {
+        self.walk();
+    }
+)]
+#[solver(
+    /// Updates time derivatives of states.
+    /// This method must be user defined in `solver` macro args.
+    fn update_derivs(&mut self) {
+        self.reset_derivs();
+        let last = self.nodes.len() - 1;
+        connect_states!(
+            self,
+            (hot, nodes[0], faces[0]),
+            [nodes, links],
+            (nodes[last], cold, faces[1])
+        );
+        update_derivs!(
+            self,
+            (hot, nodes[0], faces[0]),
+            [nodes, links],
+            (nodes[last], cold, faces[1])
+        );
+    }
+)]
+#[derive(HistoryMethods, BareClone)]
+#[common_derives]
+pub struct DiscretizedWall {
+    #[skip_get]
+    pub solver_type: SolverTypes,
+    // components
+    #[use_state]
+    pub hot: ThermalReservoir,
+    #[use_state]
+    pub nodes: Vec<ThermalMass>,
+    /// links[i] connects nodes[i] to nodes[i + 1]
+    #[save_state]
+    pub links: Vec<Conductance>,
+    /// faces connect hot to nodes[0] and the last node to cold
+    #[save_state]
+    pub faces: [Conductance; 2],
+    #[use_state]
+    pub cold: ThermalReservoir,
+    // fields needed by `solver` procedural macro
+    pub t_report: Vec<f64>,
+    pub state: SystemState,
+    pub history: SystemStateHistoryVec,
+}
+
+impl Default for DiscretizedWall {
+    fn default() -> Self {
+        let n_nodes = 50;
+        let conductance = Conductance {
+            h: 100.0,
+            ..Default::default()
+        };
+        Self {
+            solver_type: SolverTypes::RK45CashKarp(Box::default()),
+            hot: ThermalReservoir {
+                state: ThermalMassState {
+                    temp: 100.0,
+                    dtemp: Default::default(),
+                },
+                history: Default::default(),
+            },
+            nodes: vec![
+                ThermalMass {
+                    c: 1.0,
+                    ..Default::default()
+                };
+                n_nodes
+            ],
+            links: vec![conductance.clone(); n_nodes - 1],
+            faces: [conductance.clone(), conductance],
+            cold: Default::default(),
+            t_report: Vec::linspace(0.0, 10.0, 51),
+            state: Default::default(),
+            history: Default::default(),
+        }
+    }
+}
+
+impl DiscretizedWall {
+    /// Returns exact node temperatures at each of `times`, measured from the
+    /// current state and assuming all capacitances and conductances are
+    /// equal to those of the first node and link
+    pub fn analytic(&self, times: &[f64]) -> Vec<Vec<f64>> {
+        let n = self.nodes.len();
+        let (c, h) = (self.nodes[0].c, self.links[0].h);
+        let (t_hot, t_cold) = (self.hot.state.temp, self.cold.state.temp);
+        let steady: Vec<f64> = (1..=n)
+            .map(|j| t_hot + (t_cold - t_hot) * j as f64 / (n + 1) as f64)
+            .collect();
+        // deviation from steady state decays in sine modes of the chain
+        let mode = |k: usize, j: usize| (PI * (k * j) as f64 / (n + 1) as f64).sin();
+        let amplitudes: Vec<f64> = (1..=n)
+            .map(|k| {
+                2. / (n + 1) as f64
+                    * (1..=n)
+                        .map(|j| (self.nodes[j - 1].state.temp - steady[j - 1]) * mode(k, j))
+                        .sum::<f64>()
+            })
+            .collect();
+        let rates: Vec<f64> = (1..=n)
+            .map(|k| 2. * h / c * (1. - (PI * k as f64 / (n + 1) as f64).cos()))
+            .collect();
+        times
+            .iter()
+            .map(|t| {
+                (1..=n)
+                    .map(|j| {
+                        steady[j - 1]
+                            + (1..=n)
+                                .map(|k| amplitudes[k - 1] * (-rates[k - 1] * t).exp() * mode(k, j))
+                                .sum::<f64>()
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+pub fn run_discretized_wall() {
+    let mut sys = DiscretizedWall::default();
+    let t_elapsed = time_it!(sys.walk());
+    println!(
+        "Discretized wall elapsed time: {} μs",
+        t_elapsed.as_micros()
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_discretized_wall_against_analytic() {
+        let mut sys = DiscretizedWall::default();
+        let analytic = sys.analytic(&sys.t_report);
+        sys.walk();
+        for (i, node) in sys.nodes.iter().enumerate() {
+            assert_eq!(node.history.temp.len(), sys.t_report.len());
+            for (temp, exact) in node.history.temp.iter().zip(&analytic) {
+                assert!((temp - exact[i]).abs() < 1e-3);
+            }
+        }
+        assert_eq!(sys.faces[1].history.q.len(), sys.t_report.len());
+    }
+
+    #[test]
+    fn test_discretized_wall_names() {
+        let sys = DiscretizedWall::default();
+        let names = sys.state_names();
+        assert_eq!(names.len(), 52);
+        assert_eq!(names[1], "nodes.0");
+        assert_eq!(names[50], "nodes.49");
+        let columns = sys.state_column_names();
+        assert!(columns.contains(&"nodes.49.temp".to_string()));
+        assert!(columns.contains(&"faces.1.q".to_string()));
+        assert_eq!(columns.len(), sys.state_column_values().len());
+        let bare = sys.bare_clone();
+        assert_eq!(bare.nodes.len(), 50);
+    }
+}
@@ -0,0 +1,170 @@
+use crate::components::*;
+use crate::imports::*;
+
+/// Thermal mass `m1` heated by `q1` and losing heat to ambient `amb`
+/// through `h1`
+#[pyo3_api(
+    #[new]
+    fn __new__(
+        solver_type: String,
+        m1: ThermalMass,
+        amb: ThermalReservoir,
+        h1: Conductance,
+        q1: HeatSource,
+        t_report: Vec<f64>,
+    ) -> Self {
+        Self {
+            solver_type: SolverTypes::from_json(&solver_type).unwrap(),
+            m1,
+            amb,
+            h1,
+            q1,
+            t_report,
+            state: Default::default(),
+            history: Default::default(),
+        }
+    }
+
+    #[getter]
+    fn get_solver_type(&self) -> String {
+        self.solver_type.to_json()
+    }
+
+    #[pyo3(name = "walk")]
+    fn walk_py(&mut self) {
+        self.walk();
+    }
+)]
+#[solver(
+    /// Updates time derivatives of states.
+    /// This method must be user defined in `solver` macro args.
+    fn update_derivs(&mut self) {
+        self.reset_derivs();
+        connect_states!(self, (m1, amb, h1), (m1, q1));
+        update_derivs!(self, (m1, amb, h1), (m1, q1));
+    }
+)]
+#[derive(HistoryMethods, BareClone)]
+#[common_derives]
+pub struct HeatedMass {
+    #[skip_get]
+    pub solver_type: SolverTypes,
+    // components
+    #[use_state]
+    pub m1: ThermalMass,
+    #[use_state]
+    pub amb: ThermalReservoir,
+    #[save_state]
+    pub h1: Conductance,
+    /// q1 heats m1
+    #[save_state]
+    #[breakpoints]
+    pub q1: HeatSource,
+    // fields needed by `solver` procedural macro
+    pub t_report: Vec<f64>,
+    pub state: SystemState,
+    pub history: SystemStateHistoryVec,
+}
+
+impl Default for HeatedMass {
+    fn default() -> Self {
+        Self {
+            solver_type: SolverTypes::RK45CashKarp(Box::default()),
+            m1: ThermalMass {
+                c: 100.0,
+                state: ThermalMassState {
+                    temp: 20.0,
+                    dtemp: Default::default(),
+                },
+                history: Default::default(),
+            },
+            amb: ThermalReservoir {
+                state: ThermalMassState {
+                    temp: 20.0,
+                    dtemp: Default::default(),
+                },
+                history: Default::default(),
+            },
+            h1: Conductance {
+                h: 2.0,
+                ..Default::default()
+            },
+            // heater switched on part way through a report interval
+            q1: HeatSource::signal(SignalTypes::Step(Step {
+                time: 15.0,
+                before: 0.0,
+                after: 50.0,
+            })),
+            t_report: Vec::linspace(0.0, 300.0, 31),
+            state: Default::default(),
+            history: Default::default(),
+        }
+    }
+}
+
+pub fn run_heated_mass() {
+    let mut sys = HeatedMass::default();
+    let t_elapsed = time_it!(sys.walk());
+    println!("Heated mass elapsed time: {} μs", t_elapsed.as_micros());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_heated_mass_against_analytic() {
+        let mut sys = HeatedMass::default();
+        sys.walk();
+        let (c, h, q, t_on) = (100.0, 2.0, 50.0, 15.0);
+        for (t, temp) in sys.history.time.iter().zip(&sys.m1.history.temp) {
+            let rise = if *t < t_on {
+                0.0
+            } else {
+                q / h * (1. - f64::exp(-h * (t - t_on) / c))
+            };
+            assert!((temp - 20.0 - rise).abs() < 1e-4);
+        }
+        assert_eq!(sys.q1.history.flow[1], 0.0);
+        assert_eq!(*sys.q1.history.flow.last().unwrap(), q);
+    }
+
+    #[test]
+    fn test_heat_source_kinds() {
+        // proportional controller holding m1 toward 50 °C, which settles
+        // where heating balances loss to ambient
+        let mut sys = HeatedMass {
+            q1: HeatSource::function(|_t, temp| 10. * (50. - temp)),
+            ..Default::default()
+        };
+        sys.walk();
+        let temp_steady = (10. * 50. + 2. * 20.) / (10. + 2.);
+        assert!((sys.m1.state.temp - temp_steady).abs() < 1e-4);
+        // closures are shared by clones but cannot be restored
+        assert_eq!(sys.q1, sys.q1.clone());
+        assert_ne!(sys.q1, HeatSource::function(|_t, temp| 10. * (50. - temp)));
+        assert!(HeatedMass::from_yaml(&sys.to_yaml()).is_err());
+        assert!(HeatedMass::from_bincode(&sys.to_bincode()).is_err());
+        assert!(with_patches(&sys, &["{}".into()]).is_err());
+
+        let mut sys = HeatedMass {
+            q1: HeatSource::constant(50.0),
+            ..Default::default()
+        };
+        sys.walk();
+        assert!((sys.m1.state.temp - 20.0 - 50.0 / 2.0).abs() < 0.1);
+
+        let yaml = "signal: !Sine {amplitude: 2.0, frequency: 1.0}
+state: {flow: 0.0}
+history: {flow: []}";
+        let source = HeatSource::from_yaml(yaml).unwrap();
+        assert_eq!(
+            source.signal,
+            SignalTypes::Sine(Sine {
+                amplitude: 2.0,
+                frequency: 1.0,
+                phase: 0.0,
+            })
+        );
+    }
+}
@@ -0,0 +1,130 @@
+use crate::components::*;
+use crate::imports::*;
+
+/// Room air `room` heated by `heater` and losing heat through layered wall
+/// `wall` to the outdoors `outdoors`
+#[pyo3_api(
+    #[new]
+    fn __new__(
+        solver_type: String,
+        room: ThermalMass,
+        heater: HeatSource,
+        wall: LayeredWall,
+        outdoors: ThermalReservoir,
+        t_report: Vec<f64>,
+    ) -> Self {
+        Self {
+            solver_type: SolverTypes::from_json(&solver_type).unwrap(),
+            room,
+            heater,
+            wall,
+            outdoors,
+            t_report,
+            state: Default::default(),
+            history: Default::default(),
+        }
+    }
+
+    #[getter]
+    fn get_solver_type(&self) -> String {
+        self.solver_type.to_json()
+    }
+
+    #[pyo3(name = "walk")]
+    fn walk_py(&mut self) {
+        self.walk();
+    }
+)]
+#[solver(
+    /// Updates time derivatives of states.
+    /// This method must be user defined in `solver` macro args.
+    fn update_derivs(&mut self) {
+        self.reset_derivs();
+        connect_states!(self, (room, heater));
+        update_derivs!(self, (room, heater));
+        self.wall.connect_faces(&mut self.room, &mut self.outdoors);
+    }
+)]
+#[derive(HistoryMethods, BareClone)]
+#[common_derives]
+pub struct HeatedRoom {
+    #[skip_get]
+    pub solver_type: SolverTypes,
+    // components
+    #[use_state]
+    pub room: ThermalMass,
+    #[save_state]
+    pub heater: HeatSource,
+    /// contributes one state per layer
+    #[use_state]
+    pub wall: LayeredWall,
+    #[use_state]
+    pub outdoors: ThermalReservoir,
+    // fields needed by `solver` procedural macro
+    pub t_report: Vec<f64>,
+    pub state: SystemState,
+    pub history: SystemStateHistoryVec,
+}
+
+impl Default for HeatedRoom {
+    fn default() -> Self {
+        Self {
+            solver_type: SolverTypes::RK45CashKarp(Box::default()),
+            room: ThermalMass {
+                c: 1e4,
+                state: ThermalMassState {
+                    temp: 10.0,
+                    dtemp: Default::default(),
+                },
+                history: Default::default(),
+            },
+            heater: HeatSource::constant(500.0),
+            wall: LayeredWall::new(5, 2e4, 100.0, 5.0).unwrap(),
+            outdoors: ThermalReservoir::default(),
+            t_report: Vec::linspace(0.0, 1e5, 101),
+            state: Default::default(),
+            history: Default::default(),
+        }
+    }
+}
+
+pub fn run_heated_room() {
+    let mut sys = HeatedRoom::default();
+    let t_elapsed = time_it!(sys.walk());
+    println!("Heated room elapsed time: {} μs", t_elapsed.as_micros());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_heated_room_steady_state() {
+        let mut sys = HeatedRoom::default();
+        assert_eq!(
+            sys.state_names(),
+            vec!["room", "wall.0", "wall.1", "wall.2", "wall.3", "wall.4", "outdoors"]
+        );
+        assert_eq!(sys.states().len(), 7);
+        sys.walk();
+        // heater output crosses six equal conductances in series
+        let dtemp = 500.0 / 100.0;
+        assert!((sys.room.state.temp - 6. * dtemp).abs() < 1e-3);
+        for (i, temp) in sys.wall.state.temps.iter().enumerate() {
+            assert!((temp - (5 - i) as f64 * dtemp).abs() < 1e-3);
+        }
+        assert!((sys.wall.state.q1 - 500.0).abs() < 1e-2);
+        assert_eq!(sys.wall.history.temps.len(), sys.t_report.len());
+        assert_eq!(sys.wall.history.temps[0], vec![5.0; 5]);
+
+        // without layers, the room is not connected to the outdoors
+        assert!(LayeredWall::new(0, 2e4, 100.0, 5.0).is_err());
+        let mut sys = HeatedRoom {
+            wall: LayeredWall::default(),
+            ..Default::default()
+        };
+        sys.walk();
+        assert_eq!(sys.wall.state.q1, 0.0);
+        assert!(sys.room.state.temp > 6. * dtemp);
+    }
+}
@@ -1,5 +1,15 @@
+pub mod building_sys;
 pub mod components;
+pub mod cooling_plate_sys;
+pub mod coolant_loop_sys;
+pub mod discretized_wall_sys;
+pub mod heated_mass_sys;
+pub mod heated_room_sys;
 pub mod imports;
+pub mod mass_spring_damper_sys;
+pub mod network_sys;
+pub mod rc_ladder_sys;
+pub mod rlc_sys;
 pub mod three_thermal_mass_sys;
 pub mod three_thrml_mass_w_bc_sys;
 pub mod tests;
\ No newline at end of file
@@ -17,6 +17,16 @@ fn main() {
     }
     three_thermal_mass_sys::run_three_tm_sys(overwrite_benchmarks);
     three_thrml_mass_w_bc_sys::run_three_tm_w_bc_sys();
+    rc_ladder_sys::run_rc_ladder();
+    rlc_sys::run_parallel_rlc();
+    coolant_loop_sys::run_coolant_loop();
+    mass_spring_damper_sys::run_mass_spring_damper();
+    cooling_plate_sys::run_cooling_plate();
+    heated_mass_sys::run_heated_mass();
+    heated_room_sys::run_heated_room();
+    discretized_wall_sys::run_discretized_wall();
+    building_sys::run_building();
+    network_sys::run_network();
 
     // Creating small step euler baseline to compare to other methods
     let mut overwrite_baseline = false;
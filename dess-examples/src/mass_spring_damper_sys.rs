@@ -0,0 +1,165 @@
+use crate::components::*;
+use crate::imports::*;
+
+/// Mass `m1` suspended from fixed ground `gnd` by spring `k1` and damper `b1`
+#[pyo3_api(
+    #[new]
+    fn __new__(
+        solver_type: String,
+        gnd: FixedGround,
+        m1: Mass,
+        k1: Spring,
+        b1: Damper,
+        t_report: Vec<f64>,
+    ) -> Self {
+        Self {
+            solver_type: SolverTypes::from_json(&solver_type).unwrap(),
+            gnd,
+            m1,
+            k1,
+            b1,
+            t_report,
+            state: Default::default(),
+            history: Default::default(),
+        }
+    }
+
+    #[getter]
+    fn get_solver_type(&self) -> String {
+        self.solver_type.to_json()
+    }
+
+    #[pyo3(name = "walk")]
+    fn walk_py(&mut self) {
+        self.walk();
+    }
+)]
+#[solver(
+    /// Updates time derivatives of states.
+    /// This method must be user defined in `solver` macro args.
+    fn update_derivs(&mut self) {
+        self.reset_derivs();
+        connect_states!(self, (m1, gnd, k1), (m1, gnd, b1));
+        update_derivs!(self, (m1, gnd, k1), (m1, gnd, b1));
+    }
+)]
+#[derive(HistoryMethods, BareClone)]
+#[common_derives]
+pub struct MassSpringDamper {
+    #[skip_get]
+    pub solver_type: SolverTypes,
+    // components
+    #[use_state]
+    pub gnd: FixedGround,
+    #[use_state]
+    pub m1: Mass,
+    /// spring force is a state in its own right
+    #[use_flow_state]
+    pub k1: Spring,
+    #[save_state]
+    pub b1: Damper,
+    // fields needed by `solver` procedural macro
+    pub t_report: Vec<f64>,
+    pub state: SystemState,
+    pub history: SystemStateHistoryVec,
+}
+
+impl Default for MassSpringDamper {
+    fn default() -> Self {
+        Self {
+            solver_type: SolverTypes::RK45CashKarp(Box::default()),
+            gnd: Default::default(),
+            m1: Mass {
+                m: 2.0,
+                ..Default::default()
+            },
+            // released from 0.1 m compression
+            k1: Spring {
+                k: 50.0,
+                state: SpringState {
+                    f: 5.0,
+                    df: Default::default(),
+                },
+                history: Default::default(),
+            },
+            b1: Damper {
+                b: 1.0,
+                ..Default::default()
+            },
+            t_report: Vec::linspace(0.0, 5.0, 51),
+            state: Default::default(),
+            history: Default::default(),
+        }
+    }
+}
+
+impl MassSpringDamper {
+    /// Returns exact spring compression at each of `times`, measured from the
+    /// current state and assuming the system is underdamped
+    pub fn analytic(&self, times: &[f64]) -> Vec<f64> {
+        let (m, k, b) = (self.m1.m, self.k1.k, self.b1.b);
+        let x0 = self.k1.deflection();
+        let v0 = self.m1.state.v;
+        let alpha = b / (2. * m);
+        let omega_d = (k / m - alpha * alpha).sqrt();
+        times
+            .iter()
+            .map(|t| {
+                (-alpha * t).exp()
+                    * (x0 * (omega_d * t).cos() + (v0 + alpha * x0) / omega_d * (omega_d * t).sin())
+            })
+            .collect()
+    }
+}
+
+pub fn run_mass_spring_damper() {
+    let mut sys = MassSpringDamper::default();
+    let t_elapsed = time_it!(sys.walk());
+    println!(
+        "Mass-spring-damper elapsed time: {} μs",
+        t_elapsed.as_micros()
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mass_spring_damper_against_analytic() {
+        let mut sys = MassSpringDamper::default();
+        let analytic = sys.analytic(&sys.t_report);
+        sys.walk();
+        for (f, x) in sys.k1.history.f.iter().zip(&analytic) {
+            assert!((f / sys.k1.k - x).abs() < 1e-5);
+        }
+        assert!(sys.gnd.history.v.iter().all(|v| *v == 0.0));
+    }
+
+    #[test]
+    fn test_rotational_components() {
+        let shaft = Inertia {
+            j: 0.5,
+            state: InertiaState {
+                omega: 4.0,
+                domega: 0.0,
+            },
+            ..Default::default()
+        };
+        let mut torsion = TorsionSpring {
+            k: 10.0,
+            ..Default::default()
+        };
+        torsion.set_flow(&shaft, &FixedGround::default());
+        assert_eq!(torsion.flow_deriv(), 40.0);
+        torsion.step_flow_state_by_dt(&0.1);
+        assert_eq!(torsion.state.tau, 4.0);
+        assert_eq!(torsion.twist(), 0.4);
+        let mut damper = Damper {
+            b: 0.25,
+            ..Default::default()
+        };
+        damper.set_flow(&FixedGround::default(), &shaft);
+        assert_eq!(damper.flow(), -1.0);
+    }
+}
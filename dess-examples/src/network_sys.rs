@@ -0,0 +1,170 @@
+use crate::imports::*;
+
+/// Same topology, parameters, and initial conditions as
+/// [System3TM](crate::three_thermal_mass_sys::System3TM), defined at runtime
+/// as a [Network]
+pub const THREE_TM_NETWORK: &str = "solver_type: !RK4Fixed {dt: 0.005}
+nodes:
+- !ThermalMass {c: 1.0}
+- !ThermalMass {c: 2.0, state: {temp: 10.0, dtemp: 0.0}}
+- !ThermalMass {c: 1.5, state: {temp: 12.0, dtemp: 0.0}}
+edges:
+- from: 0
+  to: 1
+  conductance: !Conductance {h: 5.0}
+- from: 1
+  to: 2
+  conductance: !Conductance {h: 5.0}
+t_report: [0.0, 0.1, 0.2, 0.3, 0.4, 0.5, 0.6, 0.7, 0.8, 0.9, 1.0]
+";
+
+pub fn run_network() {
+    let mut sys = Network::from_yaml(THREE_TM_NETWORK).unwrap();
+    let t_elapsed = time_it!(sys.walk());
+    println!("Network elapsed time: {} μs", t_elapsed.as_micros());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::*;
+    use crate::heated_mass_sys::HeatedMass;
+    use crate::rlc_sys::ParallelRLC;
+    use crate::three_thermal_mass_sys::System3TM;
+
+    #[test]
+    fn test_network_against_solver_system() {
+        let mut network = Network::from_yaml(THREE_TM_NETWORK).unwrap();
+        let mut sys = System3TM {
+            solver_type: network.solver_type.clone(),
+            t_report: network.t_report.clone(),
+            ..Default::default()
+        };
+        network.walk();
+        sys.walk();
+        assert_eq!(network.state_names(), vec!["nodes.0", "nodes.1", "nodes.2"]);
+        let (columns, expected) = (network.history_columns(), sys.history_columns());
+        for (name, expected_name) in [
+            ("time", "time"),
+            ("nodes.0.temp", "m1.temp"),
+            ("nodes.1.temp", "m2.temp"),
+            ("nodes.2.temp", "m3.temp"),
+            ("edges.0.q", "h12.q"),
+            ("edges.1.q", "h23.q"),
+        ] {
+            assert_eq!(columns.get(name), expected.get(expected_name));
+        }
+
+        // adaptive solvers work as well
+        let mut network = Network {
+            solver_type: SolverTypes::RK45CashKarp(Box::default()),
+            ..Network::from_yaml(THREE_TM_NETWORK).unwrap()
+        };
+        let mut sys = System3TM {
+            solver_type: SolverTypes::RK45CashKarp(Box::default()),
+            t_report: network.t_report.clone(),
+            ..Default::default()
+        };
+        network.walk();
+        sys.walk();
+        assert_eq!(network.states(), sys.states());
+    }
+
+    #[test]
+    fn test_network_serde() {
+        let network = Network::from_yaml(THREE_TM_NETWORK).unwrap();
+        assert!(network.validate().is_ok());
+        assert_eq!(Network::from_yaml(&network.to_yaml()).unwrap(), network);
+        assert_eq!(Network::from_json(&network.to_json()).unwrap(), network);
+        let bare = network.bare_clone();
+        assert_eq!(bare.nodes, network.nodes);
+
+        let mut invalid = network.clone();
+        invalid.edges[1].to = 3;
+        assert!(invalid.validate().is_err());
+        invalid.edges[1].to = 1;
+        assert!(invalid.validate().is_err());
+
+        let yaml = THREE_TM_NETWORK.replace("!Conductance", "!Spring");
+        assert!(Network::from_yaml(&yaml).is_err());
+        // invalid networks are rejected when loaded rather than when walked
+        let yaml = THREE_TM_NETWORK.replace("to: 2", "to: 3");
+        let err = Network::from_yaml(&yaml).unwrap_err();
+        assert!(err.to_string().contains("refers to node 3"), "{}", err);
+        let yaml = THREE_TM_NETWORK.replace("t_report: [0.0,", "t_report: []\n#");
+        let err = Network::from_yaml(&yaml).unwrap_err();
+        assert!(err.to_string().contains("`t_report` is empty"), "{}", err);
+        let patch = serde_json::json!({"edges": [{"from": 0, "to": 0, "conductance": {"Conductance": {"h": 5.0}}}]});
+        let err = with_patch(&network, &patch).unwrap_err();
+        assert!(err.to_string().contains("to itself"), "{}", err);
+
+        let mut invalid = network;
+        invalid.sources.push(Injection {
+            node: 3,
+            source: HeatSource::constant(1.0),
+        });
+        assert!(invalid.validate().is_err());
+    }
+
+    #[test]
+    fn test_network_flow_states_against_solver_system() {
+        let mut sys = ParallelRLC::default();
+        let mut network = Network {
+            solver_type: sys.solver_type.clone(),
+            nodes: vec![Box::new(sys.gnd.clone()), Box::new(sys.c1.clone())],
+            edges: vec![
+                Edge {
+                    from: 1,
+                    to: 0,
+                    conductance: Box::new(sys.r1.clone()),
+                },
+                Edge {
+                    from: 1,
+                    to: 0,
+                    conductance: Box::new(sys.l1.clone()),
+                },
+            ],
+            t_report: sys.t_report.clone(),
+            ..Default::default()
+        };
+        assert_eq!(network.state_names(), vec!["nodes.0", "nodes.1", "edges.1"]);
+        assert_eq!(network.storages(), sys.storages());
+        network.walk();
+        sys.walk();
+        assert_eq!(network.states(), sys.states());
+        let (columns, expected) = (network.history_columns(), sys.history_columns());
+        assert_eq!(columns.get("edges.1.i"), expected.get("l1.i"));
+        assert_eq!(Network::from_yaml(&network.to_yaml()).unwrap(), network);
+    }
+
+    #[test]
+    fn test_network_sources_against_solver_system() {
+        let mut sys = HeatedMass::default();
+        let mut network = Network {
+            solver_type: sys.solver_type.clone(),
+            nodes: vec![Box::new(sys.m1.clone()), Box::new(sys.amb.clone())],
+            edges: vec![Edge {
+                from: 0,
+                to: 1,
+                conductance: Box::new(sys.h1.clone()),
+            }],
+            sources: vec![Injection {
+                node: 0,
+                source: sys.q1.clone(),
+            }],
+            t_report: sys.t_report.clone(),
+            ..Default::default()
+        };
+        // the heater switching on is landed on rather than stepped over
+        assert_eq!(network.next_breakpoint(), Some(15.0));
+        network.walk();
+        sys.walk();
+        assert_eq!(network.states(), sys.states());
+        let (columns, expected) = (network.history_columns(), sys.history_columns());
+        assert_eq!(columns.get("time"), expected.get("time"));
+        assert_eq!(columns.get("sources.0.flow"), expected.get("q1.flow"));
+
+        let network = Network::from_yaml(&network.to_yaml()).unwrap();
+        assert_eq!(network.sources[0].source, sys.q1);
+    }
+}
@@ -0,0 +1,187 @@
+use crate::components::*;
+use crate::imports::*;
+
+/// Two-stage RC ladder driven by an ideal voltage source:
+/// `vs -- r1 -- c1 -- r2 -- c2`, with both capacitors to ground
+#[pyo3_api(
+    #[new]
+    fn __new__(
+        solver_type: String,
+        vs: VoltageSource,
+        r1: Resistor,
+        c1: Capacitor,
+        r2: Resistor,
+        c2: Capacitor,
+        t_report: Vec<f64>,
+    ) -> Self {
+        Self {
+            solver_type: SolverTypes::from_json(&solver_type).unwrap(),
+            vs,
+            r1,
+            c1,
+            r2,
+            c2,
+            t_report,
+            state: Default::default(),
+            history: Default::default(),
+        }
+    }
+
+    #[getter]
+    fn get_solver_type(&self) -> String {
+        self.solver_type.to_json()
+    }
+
+    #[pyo3(name = "walk")]
+    fn walk_py(&mut self) {
+        self.walk();
+    }
+)]
+#[solver(
+    /// Updates time derivatives of states.
+    /// This method must be user defined in `solver` macro args.
+    fn update_derivs(&mut self) {
+        self.reset_derivs();
+        connect_states!(self, (vs, c1, r1), (c1, c2, r2));
+        update_derivs!(self, (vs, c1, r1), (c1, c2, r2));
+    }
+)]
+#[derive(HistoryMethods, BareClone)]
+#[common_derives]
+pub struct RCLadder {
+    #[skip_get]
+    pub solver_type: SolverTypes,
+    // components
+    #[use_state]
+    pub vs: VoltageSource,
+    /// r1 connects vs to c1
+    #[save_state]
+    pub r1: Resistor,
+    #[use_state]
+    pub c1: Capacitor,
+    /// r2 connects c1 to c2
+    #[save_state]
+    pub r2: Resistor,
+    #[use_state]
+    pub c2: Capacitor,
+    // fields needed by `solver` procedural macro
+    pub t_report: Vec<f64>,
+    pub state: SystemState,
+    pub history: SystemStateHistoryVec,
+}
+
+impl Default for RCLadder {
+    fn default() -> Self {
+        Self {
+            solver_type: SolverTypes::RK45CashKarp(Box::default()),
+            vs: VoltageSource {
+                state: CapacitorState {
+                    v: 1.0,
+                    dv: Default::default(),
+                },
+                history: Default::default(),
+            },
+            r1: Resistor {
+                r: 1.0,
+                ..Default::default()
+            },
+            c1: Capacitor {
+                c: 1.0,
+                ..Default::default()
+            },
+            r2: Resistor {
+                r: 2.0,
+                ..Default::default()
+            },
+            c2: Capacitor {
+                c: 0.5,
+                ..Default::default()
+            },
+            t_report: Vec::linspace(0.0, 5.0, 51),
+            state: Default::default(),
+            history: Default::default(),
+        }
+    }
+}
+
+impl RCLadder {
+    /// Returns exact capacitor voltages `[c1, c2]` at each of `times`,
+    /// measured from the current state and assuming `vs` stays constant
+    pub fn analytic(&self, times: &[f64]) -> Vec<[f64; 2]> {
+        let vs = self.vs.state.v;
+        let (r1, c1, r2, c2) = (self.r1.r, self.c1.c, self.r2.r, self.c2.c);
+        // deviation from steady state `e = [v1 - vs, v2 - vs]` obeys `de/dt = A e`
+        let a = [
+            [-(1. / r1 + 1. / r2) / c1, 1. / (r2 * c1)],
+            [1. / (r2 * c2), -1. / (r2 * c2)],
+        ];
+        let e0 = [self.c1.state.v - vs, self.c2.state.v - vs];
+        // distinct real eigenvalues of `A`
+        let trace = a[0][0] + a[1][1];
+        let det = a[0][0] * a[1][1] - a[0][1] * a[1][0];
+        let disc = (trace * trace / 4. - det).sqrt();
+        let (l1, l2) = (trace / 2. + disc, trace / 2. - disc);
+        times
+            .iter()
+            .map(|t| {
+                // exp(A t) = (e^(l1 t) (A - l2 I) - e^(l2 t) (A - l1 I)) / (l1 - l2)
+                let (x1, x2) = ((l1 * t).exp(), (l2 * t).exp());
+                let exp_at = |i: usize, j: usize| {
+                    let identity = if i == j { 1. } else { 0. };
+                    (x1 * (a[i][j] - l2 * identity) - x2 * (a[i][j] - l1 * identity)) / (l1 - l2)
+                };
+                [
+                    vs + exp_at(0, 0) * e0[0] + exp_at(0, 1) * e0[1],
+                    vs + exp_at(1, 0) * e0[0] + exp_at(1, 1) * e0[1],
+                ]
+            })
+            .collect()
+    }
+}
+
+pub fn run_rc_ladder() {
+    let mut sys = RCLadder::default();
+    let t_elapsed = time_it!(sys.walk());
+    println!("RC ladder elapsed time: {} μs", t_elapsed.as_micros());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rc_ladder_against_analytic() {
+        let mut sys = RCLadder::default();
+        let analytic = sys.analytic(&sys.t_report);
+        sys.walk();
+        for (k, [v1, v2]) in analytic.iter().enumerate() {
+            assert!((sys.c1.history.v[k] - v1).abs() < 1e-5);
+            assert!((sys.c2.history.v[k] - v2).abs() < 1e-5);
+        }
+        // the far capacitor charges more slowly
+        assert!(sys.c2.state.v < sys.c1.state.v);
+        assert!(sys.c1.state.v < sys.vs.state.v);
+    }
+
+    #[test]
+    fn test_current_source() {
+        let mut source = CurrentSource {
+            i: 0.25,
+            ..Default::default()
+        };
+        source.set_flow(&VoltageSource::default(), &Capacitor::default());
+        assert_eq!(source.flow(), 0.25);
+        let mut resistor = Resistor {
+            r: 4.0,
+            ..Default::default()
+        };
+        resistor.set_flow(
+            &VoltageSource {
+                state: CapacitorState { v: 2.0, dv: 0.0 },
+                ..Default::default()
+            },
+            &Capacitor::default(),
+        );
+        assert_eq!(resistor.flow(), 0.5);
+    }
+}
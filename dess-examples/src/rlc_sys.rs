@@ -0,0 +1,144 @@
+use crate::components::*;
+use crate::imports::*;
+
+/// Parallel RLC tank: capacitor `c1`, resistor `r1`, and inductor `l1` each
+/// connect the same node to ground, so that charge oscillates between `c1`
+/// and `l1` while `r1` damps it
+#[pyo3_api(
+    #[new]
+    fn __new__(
+        solver_type: String,
+        gnd: VoltageSource,
+        c1: Capacitor,
+        r1: Resistor,
+        l1: Inductor,
+        t_report: Vec<f64>,
+    ) -> Self {
+        Self {
+            solver_type: SolverTypes::from_json(&solver_type).unwrap(),
+            gnd,
+            c1,
+            r1,
+            l1,
+            t_report,
+            state: Default::default(),
+            history: Default::default(),
+        }
+    }
+
+    #[getter]
+    fn get_solver_type(&self) -> String {
+        self.solver_type.to_json()
+    }
+
+    #[pyo3(name = "walk")]
+    fn walk_py(&mut self) {
+        self.walk();
+    }
+)]
+#[solver(
+    /// Updates time derivatives of states.
+    /// This method must be user defined in `solver` macro args.
+    fn update_derivs(&mut self) {
+        self.reset_derivs();
+        connect_states!(self, (c1, gnd, r1), (c1, gnd, l1));
+        update_derivs!(self, (c1, gnd, r1), (c1, gnd, l1));
+    }
+)]
+#[derive(HistoryMethods, BareClone)]
+#[common_derives]
+pub struct ParallelRLC {
+    #[skip_get]
+    pub solver_type: SolverTypes,
+    // components
+    #[use_state]
+    pub gnd: VoltageSource,
+    #[use_state]
+    pub c1: Capacitor,
+    #[save_state]
+    pub r1: Resistor,
+    /// current in `l1` is a state in its own right
+    #[use_flow_state]
+    pub l1: Inductor,
+    // fields needed by `solver` procedural macro
+    pub t_report: Vec<f64>,
+    pub state: SystemState,
+    pub history: SystemStateHistoryVec,
+}
+
+impl Default for ParallelRLC {
+    fn default() -> Self {
+        Self {
+            solver_type: SolverTypes::RK45CashKarp(Box::default()),
+            gnd: Default::default(),
+            c1: Capacitor {
+                c: 1.0,
+                state: CapacitorState {
+                    v: 1.0,
+                    dv: Default::default(),
+                },
+                history: Default::default(),
+            },
+            r1: Resistor {
+                r: 5.0,
+                ..Default::default()
+            },
+            l1: Inductor {
+                l: 0.25,
+                ..Default::default()
+            },
+            t_report: Vec::linspace(0.0, 5.0, 51),
+            state: Default::default(),
+            history: Default::default(),
+        }
+    }
+}
+
+impl ParallelRLC {
+    /// Returns exact voltage of `c1` at each of `times`, measured from the
+    /// current state and assuming the circuit is underdamped
+    pub fn analytic(&self, times: &[f64]) -> Vec<f64> {
+        let (c, r, l) = (self.c1.c, self.r1.r, self.l1.l);
+        let v0 = self.c1.state.v - self.gnd.state.v;
+        let dv0 = (-v0 / r - self.l1.state.i) / c;
+        let alpha = 1. / (2. * r * c);
+        let omega_d = (1. / (l * c) - alpha * alpha).sqrt();
+        let b = (dv0 + alpha * v0) / omega_d;
+        times
+            .iter()
+            .map(|t| {
+                self.gnd.state.v
+                    + (-alpha * t).exp() * (v0 * (omega_d * t).cos() + b * (omega_d * t).sin())
+            })
+            .collect()
+    }
+}
+
+pub fn run_parallel_rlc() {
+    let mut sys = ParallelRLC::default();
+    let t_elapsed = time_it!(sys.walk());
+    println!("Parallel RLC elapsed time: {} μs", t_elapsed.as_micros());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parallel_rlc_against_analytic() {
+        let mut sys = ParallelRLC::default();
+        assert_eq!(ParallelRLC::default().state_names(), vec!["gnd", "c1", "l1"]);
+        assert_eq!(sys.states(), vec![0.0, 1.0, 0.0]);
+        assert_eq!(sys.storages(), vec![f64::INFINITY, 1.0, 0.25]);
+
+        let analytic = sys.analytic(&sys.t_report);
+        sys.walk();
+        for (v_sys, v_analytic) in sys.c1.history.v.iter().zip(&analytic) {
+            assert!((v_sys - v_analytic).abs() < 1e-4);
+        }
+        // inductor current history is saved alongside the capacitor voltage
+        assert_eq!(sys.l1.history.len(), sys.c1.history.len());
+        assert!(sys.l1.history.i.iter().any(|i| *i > 0.1));
+        assert!(sys.l1.history.i.iter().any(|i| *i < -0.1));
+    }
+}
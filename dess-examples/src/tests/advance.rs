@@ -0,0 +1,49 @@
+use crate::imports::*;
+use crate::three_thermal_mass_sys::*;
+
+#[test]
+fn test_advance() {
+    // stepping through `t_report` externally matches `walk`
+    let mut sys_walk = mock_heuns_sys();
+    sys_walk.walk();
+    let mut sys = mock_heuns_sys();
+    for t in sys.t_report.clone().into_iter().skip(1) {
+        sys.advance_to(t);
+    }
+    assert_eq!(sys.history, sys_walk.history);
+    assert_eq!(sys.m3.history, sys_walk.m3.history);
+
+    // `t_report` is not needed and adaptive `dt` is not reset by short calls
+    let mut sys_walk = mock_rk45_sys();
+    sys_walk.walk();
+    let mut sys = System3TM {
+        t_report: vec![],
+        ..mock_rk45_sys()
+    };
+    sys.advance_by(0.5);
+    let dt_proposed = sys.sc().unwrap().state.dt;
+    sys.advance_by(1e-6);
+    assert!(sys.sc().unwrap().state.dt >= dt_proposed);
+    sys.advance_to(1.0);
+    assert_eq!(sys.history.len(), 4);
+    assert_eq!(sys.state.time, 1.0);
+    assert!((sys.m1.state.temp - sys_walk.m1.state.temp).abs() < 1e-4);
+
+    // times already reached save nothing, and `walk` continues from the
+    // first report time after the current one
+    let mut sys_walk = mock_heuns_sys();
+    sys_walk.walk();
+    let mut sys = mock_heuns_sys();
+    sys.advance_to(0.51);
+    sys.advance_to(0.51);
+    sys.advance_to(0.3);
+    assert_eq!(sys.history.time, [0.0, 0.51]);
+    sys.walk();
+    let n_after = sys.t_report.iter().filter(|t| **t > 0.51).count();
+    assert_eq!(sys.history.len(), 2 + n_after);
+    assert_eq!(
+        sys.history.time[2..],
+        sys_walk.history.time[sys_walk.history.len() - n_after..]
+    );
+    assert!((sys.m1.state.temp - sys_walk.m1.state.temp).abs() < 1e-4);
+}
@@ -0,0 +1,57 @@
+use crate::imports::*;
+use crate::three_thermal_mass_sys::*;
+
+#[test]
+fn test_history_columns() {
+    let mut sys = mock_rk45_sys();
+    sys.walk();
+    let columns = sys.history_columns();
+    assert_eq!(columns.names(), sys.state_column_names().as_slice());
+    assert_eq!(columns.n_rows(), sys.history.len());
+    assert_eq!(columns.get("time").unwrap(), sys.history.time.as_slice());
+    assert_eq!(
+        columns.get("m2.temp").unwrap(),
+        sys.m2.history.temp.as_slice()
+    );
+    assert_eq!(columns.get("h23.q").unwrap(), sys.h23.history.q.as_slice());
+    assert!(columns.get("m4.temp").is_none());
+    assert!(columns.to_json().starts_with("{\"i\":[1.0,2.0,"));
+
+    // CSV export matches streamed output
+    let csv_file = std::env::temp_dir().join("dess_test_history_columns.csv");
+    let mut sys_streamed = mock_rk45_sys();
+    sys_streamed
+        .sink
+        .attach(CsvSink::from_path(&csv_file).unwrap(), true);
+    sys_streamed.walk();
+    assert_eq!(
+        columns.to_csv(),
+        std::fs::read_to_string(&csv_file).unwrap()
+    );
+}
+
+#[cfg(feature = "arrow")]
+#[test]
+fn test_arrow_export() {
+    use dess::arrow::parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
+    let mut sys = mock_rk4fixed_sys();
+    sys.walk();
+    let columns = sys.history_columns();
+    let batch = columns.to_record_batch().unwrap();
+    assert_eq!(batch.num_columns(), columns.len());
+    assert_eq!(batch.num_rows(), columns.n_rows());
+
+    let parquet_file = std::env::temp_dir().join("dess_test_arrow_export.parquet");
+    columns
+        .to_file(parquet_file.as_os_str().to_str().unwrap())
+        .unwrap();
+    let batches =
+        ParquetRecordBatchReaderBuilder::try_new(std::fs::File::open(&parquet_file).unwrap())
+            .unwrap()
+            .build()
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+    assert_eq!(batches, vec![batch]);
+}
@@ -0,0 +1,36 @@
+use crate::imports::*;
+use crate::three_thermal_mass_sys::*;
+
+#[test]
+fn test_sweep() {
+    let base = mock_rk45_sys();
+    let mut sweep = Sweep::from_yaml(
+        "
+        parameters:
+          - {path: m2.c, min: 1.0, max: 3.0}
+          - {path: /h12/h, min: 2.0, max: 8.0}
+        sampling: !FullFactorial {levels: 3}
+        metrics:
+          - !Final {column: m3.temp}
+          - !Max {column: h12.q}
+        ",
+    )
+    .unwrap();
+
+    let table = sweep.run(&base).unwrap();
+    assert_eq!(table.n_rows(), 9);
+    assert_eq!(
+        table.names(),
+        ["m2.c", "/h12/h", "final(m3.temp)", "max(h12.q)"]
+    );
+    assert_eq!(table.get("m2.c").unwrap()[..4], [1.0, 1.0, 1.0, 2.0]);
+    assert_eq!(table.get("/h12/h").unwrap()[..4], [2.0, 5.0, 8.0, 2.0]);
+    let mut sys = base.clone();
+    sys.m2.c = 2.0;
+    sys.h12.h = 2.0;
+    sys.walk();
+    assert_eq!(table.get("final(m3.temp)").unwrap()[3], sys.m3.state.temp);
+
+    sweep.parameters[0].path = "m2.x".into();
+    assert!(sweep.run(&base).is_err());
+}
@@ -0,0 +1,51 @@
+use crate::imports::*;
+use crate::three_thermal_mass_sys::*;
+
+#[test]
+fn test_ensemble() {
+    let mut base = mock_rk45_sys();
+    let csv_file = std::env::temp_dir().join("dess_test_ensemble.csv");
+    base.sink
+        .attach(CsvSink::from_path(&csv_file).unwrap(), false);
+    let capacitances = [0.5, 1.0, 2.0];
+    // clones are walked without the sink of `base`
+    let systems = ensemble(&base, &capacitances, |sys, c| sys.m1.c = *c);
+    let columns = ensemble_columns(&base, &capacitances, |sys, c| sys.m1.c = *c);
+    let patches: Vec<String> = capacitances
+        .iter()
+        .map(|c| format!("{{\"m1\": {{\"c\": {}}}}}", c))
+        .collect();
+    let patched = walk_all(with_patches(&base, &patches).unwrap()).unwrap();
+    // plain clones would interleave rows in the same sink
+    assert!(walk_all(vec![base.clone(), base.clone()]).is_err());
+    base.sink.detach().unwrap();
+    std::fs::remove_file(&csv_file).unwrap();
+
+    for (i, c) in capacitances.iter().enumerate() {
+        let mut sys = base.clone();
+        sys.m1.c = *c;
+        sys.walk();
+        assert!(!systems[i].sink.is_attached());
+        assert_eq!(systems[i], sys);
+        assert_eq!(patched[i], sys);
+        assert_eq!(columns[i], sys.history_columns());
+    }
+    assert!(with_patches(&base, &["{\"m1\": {\"c\": \"a\"}}".into()]).is_err());
+
+    // JSON has no NaN or infinity
+    let mut infinite = base.clone();
+    infinite.m3.c = f64::INFINITY;
+    let err = with_patches(&infinite, &patches).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "`m3.c` is .inf, which cannot be represented in JSON"
+    );
+    assert!(with_path_values(&base, &["m1.c"], &[vec![f64::NAN]]).is_err());
+    // `state.i` is a `usize`
+    let err = with_path_values(&base, &["state.i"], &[vec![1.5]]).unwrap_err();
+    assert!(err.to_string().contains("`state.i`"));
+    let err = with_path_values(&base, &["state.i"], &[vec![-1.0]]).unwrap_err();
+    assert!(err.to_string().contains("state.i"));
+    let sys = &with_path_values(&base, &["state.i"], &[vec![2.0]]).unwrap()[0];
+    assert_eq!(sys.state.i, 2);
+}
@@ -0,0 +1,37 @@
+use crate::components::*;
+use crate::three_thermal_mass_sys::*;
+
+#[test]
+fn test_history_vec_collection_api() {
+    let mut sys = mock_heuns_sys();
+    sys.walk();
+    let n = sys.history.len();
+
+    let last = sys.history.get(n - 1).unwrap();
+    assert_eq!(last, sys.state);
+    assert!(sys.history.get(n).is_none());
+    assert_eq!(sys.m1.history.iter().count(), n);
+    assert_eq!(
+        sys.m1.history.iter().map(|s| s.temp).collect::<Vec<_>>(),
+        sys.m1.history.temp
+    );
+
+    let sliced = sys.history.slice(2..5);
+    assert_eq!(sliced.len(), 3);
+    assert_eq!(sliced.get(0), sys.history.get(2));
+    assert_eq!(sys.history.slice(..).len(), n);
+
+    let t_start = sys.history.time[2];
+    let t_end = sys.history.time[4];
+    assert_eq!(sys.history.slice_time(t_start, t_end), sliced);
+
+    let collected: ThermalMassStateHistoryVec = sys.m2.history.clone().into_iter().collect();
+    assert_eq!(collected, sys.m2.history);
+
+    let mut history = sys.h12.history.slice(..2);
+    history.extend(sys.h12.history.slice(2..));
+    assert_eq!(history, sys.h12.history);
+    history.truncate(1);
+    assert_eq!(history.len(), 1);
+    assert_eq!(history.get(0), sys.h12.history.get(0));
+}
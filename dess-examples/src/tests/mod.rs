@@ -1,5 +1,29 @@
 pub mod euler_three_thermal_mass_sys;
 pub mod euler_three_thrml_mass_w_bc_sys;
+#[cfg(test)]
+mod advance;
+#[cfg(test)]
+mod columns;
+#[cfg(all(test, feature = "doe"))]
+mod doe;
+#[cfg(test)]
+mod ensemble;
+#[cfg(test)]
+mod history_vec;
+#[cfg(all(test, feature = "monte_carlo"))]
+mod monte_carlo;
+#[cfg(test)]
+mod observers;
+#[cfg(test)]
+mod retention;
+#[cfg(test)]
+mod signals;
+#[cfg(test)]
+mod sink;
+#[cfg(test)]
+mod snapshot;
+#[cfg(test)]
+mod time_series;
 pub mod tests_core;
 pub mod dess {
     pub mod solver {}
@@ -0,0 +1,57 @@
+use crate::imports::*;
+use crate::three_thermal_mass_sys::*;
+
+#[test]
+fn test_monte_carlo() {
+    let base = mock_rk45_sys();
+    let mc = MonteCarlo::from_yaml(
+        "
+        parameters:
+          - {path: m1.c, distribution: !Normal {mean: 1.0, std_dev: 0.1}}
+          - {path: h12.h, distribution: !Uniform {min: 4.0, max: 6.0}}
+          - {path: m2.c, distribution: !LogNormal {mu: 0.7, sigma: 0.1}}
+        n: 20
+        seed: 42
+        ",
+    )
+    .unwrap();
+    assert_eq!(mc.percentiles, [5.0, 50.0, 95.0]);
+    assert_eq!(mc.samples().unwrap(), mc.samples().unwrap());
+    assert!(mc
+        .samples()
+        .unwrap()
+        .iter()
+        .all(|s| (4.0..=6.0).contains(&s[1])));
+
+    let bands = mc.run(&base).unwrap();
+    // `i` is dropped and `time` is kept as is
+    assert_eq!(bands.len(), 3 * (base.state_column_names().len() - 2) + 1);
+    assert!(bands.get("p50(i)").is_none());
+    assert_eq!(bands.get("time").unwrap(), &base.t_report);
+    assert_eq!(bands.n_rows(), base.t_report.len());
+    let (p5, p50, p95) = (
+        bands.get("p5(m1.temp)").unwrap(),
+        bands.get("p50(m1.temp)").unwrap(),
+        bands.get("p95(m1.temp)").unwrap(),
+    );
+    assert!((0..p50.len()).all(|i| p5[i] <= p50[i] && p50[i] <= p95[i]));
+    // initial temperature is not uncertain but later ones are
+    assert_eq!(p5[0], p95[0]);
+    assert!(p5[5] < p95[5]);
+
+    // runs that report past the last time stop one row early
+    let mut mc = mc;
+    mc.parameters[0] = UncertainParameter {
+        path: "t_report.9".into(),
+        distribution: Distribution::Uniform {
+            min: 0.85,
+            max: 1.5,
+        },
+    };
+    let err = mc.run(&base).unwrap_err().to_string();
+    assert!(
+        err.starts_with("Run ") && err.contains("values of `i`"),
+        "{}",
+        err
+    );
+}
@@ -0,0 +1,46 @@
+use crate::imports::*;
+use crate::three_thermal_mass_sys::*;
+
+#[test]
+fn test_observers() {
+    use std::sync::{Arc, Mutex};
+
+    // fixed step solver reports every internal step as accepted
+    let mut sys = mock_euler_sys();
+    let times = Arc::new(Mutex::new(Vec::<f64>::new()));
+    let times_clone = times.clone();
+    sys.observers.attach(move |info: &StepInfo| {
+        assert!(info.accepted);
+        assert_eq!(info.states.len(), 3);
+        times_clone.lock().unwrap().push(info.time);
+    });
+    sys.walk();
+    let times = times.lock().unwrap();
+    assert!(times.len() >= sys.t_report.len() - 1);
+    assert!(times.windows(2).all(|t| t[1] > t[0]));
+    assert_eq!(times.last(), sys.t_report.last());
+
+    // adaptive solver also reports rejected attempts
+    let mut sys = System3TM {
+        solver_type: SolverTypes::RK45CashKarp(Box::new(AdaptiveSolverConfig {
+            save: true,
+            ..Default::default()
+        })),
+        ..mock_rk45_sys()
+    };
+    let counts = Arc::new(Mutex::new((0, 0)));
+    let counts_clone = counts.clone();
+    sys.observers.attach(move |info: &StepInfo| {
+        assert!(info.solver_state.is_some());
+        let mut counts = counts_clone.lock().unwrap();
+        if info.accepted {
+            counts.0 += 1;
+        } else {
+            counts.1 += 1;
+        }
+    });
+    sys.walk();
+    let counts = counts.lock().unwrap();
+    assert_eq!(counts.0, sys.sc().unwrap().history.len());
+    assert!(counts.1 > 0);
+}
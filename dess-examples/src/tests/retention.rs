@@ -0,0 +1,60 @@
+use crate::imports::*;
+use crate::three_thermal_mass_sys::*;
+
+#[test]
+fn test_retention() {
+    let run = |retention: Retention| {
+        let mut sys = System3TM {
+            retention,
+            ..mock_rk45_sys()
+        };
+        if let SolverTypes::RK45CashKarp(sc) = &mut sys.solver_type {
+            sc.save = true;
+        }
+        sys.walk();
+        sys
+    };
+    let sys_all = run(Retention::All);
+    let n_reports = sys_all.history.len();
+    let n_steps = sys_all.sc().unwrap().history.len();
+
+    let sys = run(Retention::EveryNth { n: 3 });
+    assert_eq!(sys.history.len(), n_reports.div_ceil(3));
+    assert_eq!(sys.m1.history.len(), sys.history.len());
+    assert_eq!(sys.h23.history.len(), sys.history.len());
+    assert_eq!(sys.history.time[1], sys_all.history.time[3]);
+    assert_eq!(sys.sc().unwrap().history.len(), n_steps.div_ceil(3));
+
+    let sys = run(Retention::LastN { n: 4 });
+    assert_eq!(sys.history.len(), 4);
+    assert_eq!(sys.m3.history.len(), 4);
+    assert_eq!(sys.history.time, sys_all.history.time[n_reports - 4..]);
+    assert_eq!(
+        sys.m3.history.temp,
+        sys_all.m3.history.temp[n_reports - 4..]
+    );
+    assert_eq!(sys.sc().unwrap().history.len(), 4);
+
+    // during a run, histories are trimmed only once they exceed `2 * n`
+    let mut sys = System3TM {
+        retention: Retention::LastN { n: 4 },
+        ..mock_rk45_sys()
+    };
+    let lens: Vec<usize> = (0..10)
+        .map(|_| {
+            sys.state.i += 1;
+            sys.save_state();
+            sys.history.len()
+        })
+        .collect();
+    assert_eq!(lens, [1, 2, 3, 4, 5, 6, 7, 8, 4, 5]);
+    assert_eq!(sys.m1.history.len(), 5);
+    sys.trim_history();
+    assert_eq!(sys.history.len(), 4);
+
+    let sys = run(Retention::Discard);
+    assert!(sys.history.is_empty());
+    assert!(sys.h12.history.is_empty());
+    assert!(sys.sc().unwrap().history.is_empty());
+    assert_eq!(sys.m1.state, sys_all.m1.state);
+}
@@ -0,0 +1,116 @@
+use crate::imports::*;
+use crate::three_thrml_mass_w_bc_sys::{mock_rk45_sys, System3TMWithBC};
+
+#[test]
+fn test_signals() {
+    let pulses = PulseTrain {
+        period: 0.4,
+        width: 0.1,
+        delay: 0.2,
+        low: 1.0,
+        high: 2.0,
+    };
+    assert_eq!(pulses.value(0.1), 1.0);
+    assert_eq!(pulses.value(0.2), 2.0);
+    assert_eq!(pulses.value(0.3), 1.0);
+    assert_eq!(pulses.value(0.65), 2.0);
+    assert_eq!(pulses.next_breakpoint(0.0), Some(0.2));
+    assert!((pulses.next_breakpoint(0.25).unwrap() - 0.3).abs() < 1e-12);
+    let ramp = Ramp {
+        start_time: 1.0,
+        end_time: 2.0,
+        start_value: 0.0,
+        end_value: 4.0,
+    };
+    assert_eq!(ramp.value(0.0), 0.0);
+    assert_eq!(ramp.value(1.5), 2.0);
+    assert_eq!(ramp.value(3.0), 4.0);
+    assert_eq!(ramp.next_breakpoint(1.0), Some(2.0));
+
+    let sum = SignalTypes::from_yaml(
+        "!Sum [!Constant 1.0, !Step {time: 0.5, after: 2.0}, !Ramp {start_time: 0.2, end_time: 1.0, end_value: 1.0}]",
+    )
+    .unwrap();
+    assert_eq!(sum.value(0.0), 1.0);
+    assert_eq!(sum.value(0.6), 3.5);
+    assert_eq!(sum.next_breakpoint(0.0), Some(0.2));
+    assert_eq!(sum.next_breakpoint(0.2), Some(0.5));
+
+    // decaying chirp built into `System3TMWithBC`, attached from YAML
+    let sys_builtin = {
+        let mut sys = mock_rk45_sys();
+        sys.walk();
+        sys
+    };
+    let mut sys = System3TMWithBC {
+        m1_temp: Some(
+            SignalTypes::from_yaml(&format!(
+                "!Sum
+                  - !Constant 10.0
+                  - !Product
+                    - !Chirp {{amplitude: 3.0, start_frequency: 0.0, end_frequency: {}, duration: 1.0}}
+                    - !Exponential {{amplitude: 1.0, time_constant: 0.25}}",
+                100. / std::f64::consts::PI
+            ))
+            .unwrap(),
+        ),
+        ..mock_rk45_sys()
+    };
+    sys.walk();
+    assert_eq!(sys.m1.history.len(), sys_builtin.m1.history.len());
+    for (temp, temp_builtin) in sys.m3.history.temp.iter().zip(&sys_builtin.m3.history.temp) {
+        assert!((temp - temp_builtin).abs() < 1e-6);
+    }
+}
+
+#[test]
+fn test_signal_validation() {
+    for yaml in [
+        "!PulseTrain {period: 0.0, width: 0.1, high: 1.0}",
+        "!Chirp {amplitude: 1.0, start_frequency: 0.0, end_frequency: 1.0, duration: 0.0}",
+        "!Exponential {amplitude: 1.0, time_constant: 0.0}",
+        "!Sum [!Constant 1.0, !Exponential {amplitude: 1.0, time_constant: 0.0}]",
+        "!TimeSeries {time: [0.0, 0.0], value: [1.0, 2.0]}",
+        "!TimeSeries {time: [0.0, .nan], value: [1.0, 2.0]}",
+        "!Ramp {start_time: 2.0, end_time: 1.0, end_value: 1.0}",
+        "!Ramp {start_time: .nan, end_time: 1.0, end_value: 1.0}",
+        "!PiecewiseLinear {time: [], value: []}",
+        "!PiecewiseLinear {time: [0.0, 1.0], value: [1.0]}",
+        "!PiecewiseLinear {time: [0.0, 1.0, 0.5], value: [1.0, 2.0, 3.0]}",
+        "!PiecewiseLinear {time: [0.0, 1.0], value: [1.0, .inf]}",
+    ] {
+        assert!(SignalTypes::from_yaml(yaml).is_err(), "{}", yaml);
+    }
+    let err =
+        SignalTypes::from_yaml("!PulseTrain {period: 0.0, width: 0.1, high: 1.0}").unwrap_err();
+    assert!(
+        err.to_string().contains("`period` must be positive"),
+        "{}",
+        err
+    );
+    let err =
+        SignalTypes::from_yaml("!PiecewiseLinear {time: [0.0, 1.0, 0.5], value: [1.0, 2.0, 3.0]}")
+            .unwrap_err();
+    assert!(
+        err.to_string().contains("time[1] = 1 is followed by 0.5"),
+        "{}",
+        err
+    );
+    // instantaneous ramp is a step
+    let ramp =
+        SignalTypes::from_yaml("!Ramp {start_time: 1.0, end_time: 1.0, end_value: 1.0}").unwrap();
+    assert_eq!(ramp.value(0.5), 0.0);
+    assert_eq!(ramp.value(1.0), 1.0);
+
+    let signal = SignalTypes::Exponential(Exponential {
+        amplitude: 1.0,
+        time_constant: 0.0,
+    });
+    assert!(signal.validate().is_err());
+    assert!(
+        SignalTypes::from_yaml("!Exponential {amplitude: 1.0, time_constant: -0.5}")
+            .unwrap()
+            .validate()
+            .is_ok()
+    );
+}
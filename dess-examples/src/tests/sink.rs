@@ -0,0 +1,68 @@
+use crate::imports::*;
+use crate::three_thermal_mass_sys::*;
+
+#[test]
+fn test_csv_sink() {
+    let csv_file = std::env::temp_dir().join("dess_test_csv_sink.csv");
+
+    let mut sys = mock_euler_sys();
+    sys.sink
+        .attach(CsvSink::from_path(&csv_file).unwrap(), false);
+    sys.walk();
+    // history is only streamed
+    assert!(sys.history.is_empty());
+    assert!(sys.m1.history.is_empty());
+
+    let contents = std::fs::read_to_string(&csv_file).unwrap();
+    let mut lines = contents.lines();
+    assert_eq!(
+        lines.next().unwrap(),
+        "i,time,m1.temp,m1.dtemp,m2.temp,m2.dtemp,h12.q,m3.temp,m3.dtemp,h23.q"
+    );
+    let rows: Vec<Vec<f64>> = lines
+        .map(|line| line.split(',').map(|v| v.parse().unwrap()).collect())
+        .collect();
+
+    // streamed rows match in-memory history
+    let mut sys_vecs = mock_euler_sys();
+    sys_vecs.walk();
+    assert_eq!(rows.len(), sys_vecs.history.len());
+    assert_eq!(rows.last().unwrap(), &sys_vecs.state_column_values());
+    assert_eq!(
+        rows.iter().map(|row| row[2]).collect::<Vec<f64>>(),
+        sys_vecs.m1.history.temp
+    );
+}
+
+#[test]
+fn test_failing_sink() {
+    /// sink whose device fills up after a few rows
+    struct FullDisk {
+        rows: usize,
+    }
+    impl HistorySink for FullDisk {
+        fn write_header(&mut self, _names: &[String]) -> anyhow::Result<()> {
+            Ok(())
+        }
+        fn write_row(&mut self, _values: &[f64]) -> anyhow::Result<()> {
+            self.rows += 1;
+            anyhow::ensure!(self.rows <= 3, "No space left on device");
+            Ok(())
+        }
+    }
+
+    // run completes, with history kept in memory
+    let mut sys = mock_euler_sys();
+    sys.sink.attach(FullDisk { rows: 0 }, true);
+    sys.walk();
+    assert_eq!(sys.history.len(), sys.t_report.len());
+    let err = sys.sink.flush().unwrap_err();
+    assert!(err.to_string().contains("No space left on device"));
+    assert!(sys.sink.detach().is_err());
+
+    let checkpoint_file = std::env::temp_dir().join("dess_test_failing_sink.bin");
+    let mut sys = mock_euler_sys();
+    sys.sink.attach(FullDisk { rows: 0 }, true);
+    assert!(sys.walk_with_checkpoints(&checkpoint_file, 10).is_err());
+    assert_eq!(sys.state.i, 4);
+}
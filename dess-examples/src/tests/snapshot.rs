@@ -0,0 +1,55 @@
+use crate::imports::*;
+use crate::three_thermal_mass_sys::*;
+
+#[test]
+fn test_checkpoint_resume() {
+    let checkpoint_file = std::env::temp_dir().join("dess_test_checkpoint.bin");
+    let run = || {
+        let mut sys = mock_rk45_sys();
+        if let SolverTypes::RK45CashKarp(sc) = &mut sys.solver_type {
+            sc.save = true;
+        }
+        sys
+    };
+    let mut sys_full = run();
+    sys_full.walk();
+
+    // last checkpoint is written after 8 of 11 saved states
+    let mut sys = run();
+    sys.walk_with_checkpoints(&checkpoint_file, 4).unwrap();
+    assert_eq!(sys.to_bincode(), sys_full.to_bincode());
+
+    let mut sys_resumed = System3TM::resume(&checkpoint_file).unwrap();
+    assert_eq!(sys_resumed.state.i, 8);
+    assert_eq!(sys_resumed.history.len(), 8);
+    sys_resumed.walk();
+    assert_eq!(sys_resumed.to_bincode(), sys_full.to_bincode());
+
+    // snapshot is stored with the checkpoint
+    sys_resumed.reset().unwrap();
+    assert_eq!(sys_resumed.to_bincode(), run().to_bincode());
+    std::fs::remove_file(&checkpoint_file).unwrap();
+}
+
+#[test]
+fn test_reset_rerun() {
+    let mut sys = mock_rk45_sys();
+    assert!(sys.reset().is_err());
+    let sys_initial = sys.clone();
+    sys.walk();
+    let sys_walked = sys.clone();
+
+    sys.reset().unwrap();
+    assert_eq!(sys.to_bincode(), sys_initial.to_bincode());
+    sys.walk();
+    assert_eq!(sys.to_bincode(), sys_walked.to_bincode());
+
+    sys.rerun_with(|sys| sys.m1.c = 2.0).unwrap();
+    assert_eq!(sys.m1.c, 2.0);
+    assert_eq!(sys.history.len(), sys_walked.history.len());
+    assert_ne!(sys.m1.state.temp, sys_walked.m1.state.temp);
+
+    // parameters applied by `rerun_with` are not part of the snapshot
+    sys.reset().unwrap();
+    assert_eq!(sys.m1.c, sys_initial.m1.c);
+}
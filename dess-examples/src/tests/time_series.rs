@@ -0,0 +1,69 @@
+use crate::imports::*;
+use crate::three_thrml_mass_w_bc_sys::System3TMWithBC;
+
+#[test]
+fn test_time_series_input() {
+    use std::sync::{Arc, Mutex};
+
+    // step change in reservoir temperature at a time that is not in
+    // `t_report` is hit exactly by the adaptive solver
+    let mut sys = System3TMWithBC {
+        solver_type: SolverTypes::RK45CashKarp(Box::default()),
+        t_report: Vec::linspace(0.0, 1.0, 11),
+        m1_temp: Some(SignalTypes::TimeSeries(
+            TimeSeriesInput::new(
+                vec![0.0, 0.337],
+                vec![-1.0, 20.0],
+                Interpolation::ZeroOrderHold,
+            )
+            .unwrap(),
+        )),
+        ..Default::default()
+    };
+    let sys_unwalked = sys.clone();
+    let times = Arc::new(Mutex::new(Vec::<f64>::new()));
+    let times_clone = times.clone();
+    sys.observers.attach(move |info: &StepInfo| {
+        if info.accepted {
+            times_clone.lock().unwrap().push(info.time);
+        }
+    });
+    sys.walk();
+    assert!(times.lock().unwrap().contains(&0.337));
+    assert_eq!(sys.m1.history.temp[3], -1.0);
+    assert_eq!(sys.m1.history.temp[4], 20.0);
+
+    // `m1_temp` survives bincode, as used by checkpoints and snapshots
+    let checkpoint_file = std::env::temp_dir().join("dess_test_time_series_input.bin");
+    let mut sys_checkpointed = sys_unwalked.clone();
+    sys_checkpointed
+        .walk_with_checkpoints(&checkpoint_file, 4)
+        .unwrap();
+    let mut sys_resumed = System3TMWithBC::resume(&checkpoint_file).unwrap();
+    std::fs::remove_file(&checkpoint_file).unwrap();
+    assert_eq!(sys_resumed.m1_temp, sys_unwalked.m1_temp);
+    sys_resumed.walk();
+    assert_eq!(sys_resumed.to_bincode(), sys.to_bincode());
+    sys_resumed.reset().unwrap();
+    assert_eq!(sys_resumed, sys_unwalked);
+
+    // stages at the end of a step that lands on a jump use the value
+    // before it, so the run matches one without the jump up to there
+    let with_m1_temp = |time: Vec<f64>, value: Vec<f64>| System3TMWithBC {
+        solver_type: SolverTypes::RK4Fixed { dt: 0.05 },
+        t_report: Vec::linspace(0.0, 1.0, 11),
+        m1_temp: Some(SignalTypes::TimeSeries(
+            TimeSeriesInput::new(time, value, Interpolation::ZeroOrderHold).unwrap(),
+        )),
+        ..Default::default()
+    };
+    let mut sys_jump = with_m1_temp(vec![0.0, 0.5], vec![-1.0, 20.0]);
+    let mut sys_constant = with_m1_temp(vec![0.0], vec![-1.0]);
+    sys_jump.walk();
+    sys_constant.walk();
+    assert_eq!(
+        sys_jump.m2.history.temp[..=5],
+        sys_constant.m2.history.temp[..=5]
+    );
+    assert!(sys_jump.m2.history.temp[6] > sys_constant.m2.history.temp[6]);
+}
@@ -21,8 +21,7 @@ use crate::imports::*;
             m3, 
             h23, 
             t_report,
-            state: Default::default(),
-            history: Default::default(),
+            ..Default::default()
         }
     }
 
@@ -46,8 +45,7 @@ use crate::imports::*;
             m3, 
             h23, 
             t_report,
-            state: Default::default(),
-            history: Default::default(),
+            ..Default::default()
         }
     }
 
@@ -71,8 +69,7 @@ use crate::imports::*;
             m3, 
             h23, 
             t_report,
-            state: Default::default(),
-            history: Default::default(),
+            ..Default::default()
         }
     }
 
@@ -94,6 +91,35 @@ use crate::imports::*;
     fn walk_py(&mut self) {
         self.walk();
     }
+
+    #[pyo3(name = "advance_to")]
+    fn advance_to_py(&mut self, t: f64) {
+        self.advance_to(t);
+    }
+
+    #[pyo3(name = "advance_by")]
+    fn advance_by_py(&mut self, dt: f64) {
+        self.advance_by(dt);
+    }
+
+    #[pyo3(name = "checkpoint")]
+    fn checkpoint_py(&self, path: &str) -> PyResult<()> {
+        Ok(self.checkpoint(path)?)
+    }
+
+    #[classmethod]
+    #[pyo3(name = "resume")]
+    fn resume_py(_cls: &PyType, path: &str) -> PyResult<Self> {
+        Ok(Self::resume(path)?)
+    }
+
+    /// Returns one walked copy of `self` per JSON merge patch in `patches`,
+    /// e.g. `'{"m1": {"c": 2.0}}'`, run in parallel without holding the GIL
+    #[pyo3(name = "ensemble")]
+    fn ensemble_py(&self, py: Python, patches: Vec<String>) -> PyResult<Vec<Self>> {
+        let systems = with_patches(self, &patches)?;
+        Ok(py.allow_threads(|| walk_all(systems))?)
+    }
 )]
 #[solver(
     /// Updates time derivatives of states.
@@ -126,6 +152,26 @@ pub struct System3TM {
     pub t_report: Vec<f64>,
     pub state: SystemState,
     pub history: SystemStateHistoryVec,
+    /// limits how much history is kept in memory
+    #[serde(default)]
+    #[skip_get]
+    #[retention]
+    pub retention: Retention,
+    /// monitor every internal solver step
+    #[serde(skip)]
+    #[skip_get]
+    #[observers]
+    pub observers: Observers,
+    /// streams history to e.g. a CSV file
+    #[serde(skip)]
+    #[skip_get]
+    #[sink]
+    pub sink: Sink,
+    /// initial conditions restored by `reset`
+    #[serde(skip)]
+    #[skip_get]
+    #[snapshot]
+    pub snapshot: Snapshot,
 }
 
 impl Default for System3TM {
@@ -177,10 +223,17 @@ impl Default for System3TM {
             t_report: Vec::linspace(0.0, 1.0, 201),
             state: Default::default(),
             history: Default::default(),
+            retention: Default::default(),
+            observers: Default::default(),
+            sink: Default::default(),
+            snapshot: Default::default(),
         }
     }
 }
 
+// exposes `System3TM` to FMI co-simulation masters -- see `dess-examples-fmi`
+dess::impl_fmi_model!(System3TM, parameters: [m1.c, m2.c, m3.c, h12.h, h23.h]);
+
 pub fn mock_euler_sys() -> System3TM {
     System3TM::default()
 }
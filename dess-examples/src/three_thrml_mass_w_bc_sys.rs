@@ -21,9 +21,9 @@ use crate::imports::*;
             h12,
             m3,
             h23,
+            m1_temp: None,
             t_report,
-            state: Default::default(),
-            history: Default::default(),
+            ..Default::default()
         }
     }
 
@@ -46,9 +46,9 @@ use crate::imports::*;
             h12,
             m3,
             h23,
+            m1_temp: None,
             t_report,
-            state: Default::default(),
-            history: Default::default(),
+            ..Default::default()
         }
     }
 
@@ -71,9 +71,9 @@ use crate::imports::*;
             h12,
             m3,
             h23,
+            m1_temp: None,
             t_report,
-            state: Default::default(),
-            history: Default::default(),
+            ..Default::default()
         }
     }
 
@@ -95,17 +95,51 @@ use crate::imports::*;
     fn walk_py(&mut self) {
         self.walk();
     }
+
+    #[pyo3(name = "advance_to")]
+    fn advance_to_py(&mut self, t: f64) {
+        self.advance_to(t);
+    }
+
+    #[pyo3(name = "advance_by")]
+    fn advance_by_py(&mut self, dt: f64) {
+        self.advance_by(dt);
+    }
+
+    #[pyo3(name = "checkpoint")]
+    fn checkpoint_py(&self, path: &str) -> PyResult<()> {
+        Ok(self.checkpoint(path)?)
+    }
+
+    #[classmethod]
+    #[pyo3(name = "resume")]
+    fn resume_py(_cls: &PyType, path: &str) -> PyResult<Self> {
+        Ok(Self::resume(path)?)
+    }
+
+    /// Returns one walked copy of `self` per JSON merge patch in `patches`,
+    /// e.g. `'{"m1": {"c": 2.0}}'`, run in parallel without holding the GIL
+    #[pyo3(name = "ensemble")]
+    fn ensemble_py(&self, py: Python, patches: Vec<String>) -> PyResult<Vec<Self>> {
+        let systems = with_patches(self, &patches)?;
+        Ok(py.allow_threads(|| walk_all(systems))?)
+    }
 )]
 #[solver(
     /// Updates time derivatives of states.
     /// This method must be user defined in `solver` macro args.
     fn update_derivs(&mut self) {
         self.reset_derivs();
-        // change in temperature to test what the solver does
-        let offset = 10.0;
-        let freq = 100. * self.state.time;
-        let lag = 0.25;
-        self.m1.state.temp = offset + 3. * f64::sin(freq * self.state.time) * f64::exp(-self.state.time / lag);
+        self.m1.state.temp = match &self.m1_temp {
+            Some(m1_temp) => m1_temp.value(self.state.time),
+            None => {
+                // change in temperature to test what the solver does
+                let offset = 10.0;
+                let freq = 100. * self.state.time;
+                let lag = 0.25;
+                offset + 3. * f64::sin(freq * self.state.time) * f64::exp(-self.state.time / lag)
+            }
+        };
         //create system of equations/relationships
         connect_states!(self, (m1, m2, h12), (m2, m3, h23));
         //update derivs based on system of equations created
@@ -128,10 +162,33 @@ pub struct System3TMWithBC {
     pub m3: ThermalMass,
     #[save_state]
     pub h23: Conductance,
+    /// optional signal, e.g. a lookup table, for temperature of m1, which
+    /// replaces the built-in decaying chirp
+    #[serde(default)]
+    #[skip_get]
+    #[breakpoints]
+    pub m1_temp: Option<SignalTypes>,
     // fields needed by `solver` procedural macro
     pub t_report: Vec<f64>,
     pub state: SystemState,
     pub history: SystemStateHistoryVec,
+    // optional features, enabled by their markers
+    #[serde(default)]
+    #[skip_get]
+    #[retention]
+    pub retention: Retention,
+    #[serde(skip)]
+    #[skip_get]
+    #[observers]
+    pub observers: Observers,
+    #[serde(skip)]
+    #[skip_get]
+    #[sink]
+    pub sink: Sink,
+    #[serde(skip)]
+    #[skip_get]
+    #[snapshot]
+    pub snapshot: Snapshot,
 }
 
 impl Default for System3TMWithBC {
@@ -179,9 +236,14 @@ impl Default for System3TMWithBC {
                     q: Default::default(),
                 },
             },
+            m1_temp: None,
             t_report: Vec::linspace(0.0, 1.0, 201),
             state: Default::default(),
             history: Default::default(),
+            retention: Default::default(),
+            observers: Default::default(),
+            sink: Default::default(),
+            snapshot: Default::default(),
         }
     }
 }
@@ -1,29 +1,49 @@
 use crate::imports::*;
+use crate::utilities::{is_array, is_vec};
 
-///
+/// Derives `bare_clone` method for struct
 pub(crate) fn bare_clone_derive(input: TokenStream) -> TokenStream {
     let item_struct = syn::parse_macro_input!(input as syn::ItemStruct);
     let ident = &item_struct.ident;
     let fields = item_struct.fields;
 
-    // all fields with `use_state` or `save_state` attribute
+    // all fields with `use_state`, `use_flow_state`, or `save_state` attribute
     let has_bare_clone: Vec<bool> = fields
         .iter()
         .map(|field| {
             field
                 .attrs
                 .iter()
-                .any(|attr| attr.path.is_ident("use_state") || attr.path.is_ident("save_state"))
+                .any(|attr| {
+                    attr.path.is_ident("use_state")
+                        || attr.path.is_ident("use_flow_state")
+                        || attr.path.is_ident("save_state")
+                })
         })
         .collect();
 
-    // vec of fields that should be cloned with `bare_clone()`
+    // vec of fields that should be cloned with `bare_clone()`, element by
+    // element for vecs and arrays
     let fields_to_bare_clone = fields
         .iter()
         .zip(has_bare_clone)
         .filter(|(_f, hbc)| *hbc)
         .map(|(f, _hsv)| f.ident.as_ref().unwrap())
         .collect::<Vec<_>>();
+    let bare_clones = fields
+        .iter()
+        .filter(|f| fields_to_bare_clone.contains(&f.ident.as_ref().unwrap()))
+        .map(|f| {
+            let ident = f.ident.as_ref().unwrap();
+            if is_vec(f) {
+                quote! {new.#ident = self.#ident.iter().map(|x| x.bare_clone()).collect();}
+            } else if is_array(f) {
+                quote! {new.#ident = self.#ident.each_ref().map(|x| x.bare_clone());}
+            } else {
+                quote! {new.#ident = self.#ident.bare_clone();}
+            }
+        })
+        .collect::<Vec<_>>();
 
     let is_not_history: Vec<bool> = fields
         .iter()
@@ -47,7 +67,7 @@ pub(crate) fn bare_clone_derive(input: TokenStream) -> TokenStream {
             /// Returns a copy
             fn bare_clone(&self) -> Self {
                 let mut new = Self::default();
-                #(new.#fields_to_bare_clone = self.#fields_to_bare_clone.bare_clone();)*
+                #(#bare_clones)*
                 #(new.#fields_to_clone = self.#fields_to_clone.clone();)*
                 new
             }
@@ -1,13 +1,19 @@
 use crate::imports::*;
+use crate::utilities::{is_vec_or_array, marked_field};
 
 pub(crate) fn history_methods_derive(input: TokenStream) -> TokenStream {
     let item_struct = syn::parse_macro_input!(input as ItemStruct);
     let ident = &item_struct.ident;
     let fields = item_struct.fields;
 
-    let struct_has_state = fields
+    let state_field = fields
         .iter()
-        .any(|x| *x.ident.as_ref().unwrap() == "state");
+        .find(|x| *x.ident.as_ref().unwrap() == "state");
+    let struct_has_state = state_field.is_some();
+
+    let sink = marked_field(&fields, "sink");
+
+    let retention = marked_field(&fields, "retention");
 
     let use_state_vec: Vec<bool> = fields
         .iter()
@@ -15,25 +21,47 @@ pub(crate) fn history_methods_derive(input: TokenStream) -> TokenStream {
             field
                 .attrs
                 .iter()
-                .any(|attr| attr.path.is_ident("use_state") || attr.path.is_ident("save_state"))
+                .any(|attr| {
+                    attr.path.is_ident("use_state")
+                        || attr.path.is_ident("use_flow_state")
+                        || attr.path.is_ident("save_state")
+                })
         })
         .collect();
 
-    let fields_with_state = fields
+    // vecs and arrays of e.g. components are handled element by element
+    let (vecs_with_state, fields_with_state): (Vec<_>, Vec<_>) = fields
         .iter()
         .zip(use_state_vec)
         .filter(|(_f, hsv)| *hsv)
-        .map(|(f, _hsv)| f.ident.as_ref().unwrap())
+        .map(|(f, _hsv)| f)
+        .partition(|f| is_vec_or_array(f));
+    let vecs_with_state = vecs_with_state
+        .iter()
+        .map(|f| f.ident.as_ref().unwrap())
+        .collect::<Vec<_>>();
+    let fields_with_state = fields_with_state
+        .iter()
+        .map(|f| f.ident.as_ref().unwrap())
+        .collect::<Vec<_>>();
+    let fields_with_state_str = fields_with_state
+        .iter()
+        .map(|x| x.to_string())
+        .collect::<Vec<_>>();
+    let vecs_with_state_str = vecs_with_state
+        .iter()
+        .map(|x| x.to_string())
         .collect::<Vec<_>>();
 
     let mut impl_block = TokenStream2::default();
 
-    if struct_has_state || !fields_with_state.is_empty() {
+    if struct_has_state || !fields_with_state.is_empty() || !vecs_with_state.is_empty() {
         // struct has state and has fields with state
         let save_state_cust_doc: TokenStream2 = format!(
             "/// Saves `self.state` to `self.history` and propagtes to `save_state` in {}",
             fields_with_state
                 .iter()
+                .chain(&vecs_with_state)
                 .map(|x| format!("[Self::{}]", x))
                 .collect::<Vec<String>>()
                 .join(", ")
@@ -42,17 +70,152 @@ pub(crate) fn history_methods_derive(input: TokenStream) -> TokenStream {
         .unwrap();
 
         let self_state_push: TokenStream2 = if struct_has_state {
-            quote! {self.history.push(self.state)}
+            quote! {self.history.push(Clone::clone(&self.state))}
+        } else {
+            quote! {}
+        };
+
+        let (self_state_names, self_state_values) = match state_field {
+            Some(f) => {
+                let ty = &f.ty;
+                (
+                    quote! {names.extend(<#ty as StateColumns>::column_names());},
+                    quote! {values.extend(self.state.column_values());},
+                )
+            }
+            None => (quote! {}, quote! {}),
+        };
+
+        let self_history_columns = if struct_has_state {
+            quote! {let mut columns = self.history.columns();}
+        } else {
+            quote! {let mut columns = Columns::new();}
+        };
+
+        let self_history_keep_last = if struct_has_state {
+            quote! {self.history.keep_last(n);}
+        } else {
+            quote! {}
+        };
+
+        let sink_write = if let Some(sink) = sink {
+            quote! {
+                if self.#sink.is_attached() {
+                    // any error is kept by the sink and returned by `Sink::flush`
+                    self.#sink
+                        .write(|| self.state_column_names(), &self.state_column_values());
+                }
+            }
         } else {
             quote! {}
         };
 
+        let mut keep_conditions: Vec<TokenStream2> = Vec::new();
+        if let Some(sink) = sink {
+            keep_conditions.push(quote! {self.#sink.keep_history()});
+        }
+        if let Some(retention) = retention {
+            // `save_state` is first called by `walk` with `self.state.i == 1`
+            keep_conditions.push(quote! {self.#retention.keep(self.state.i.saturating_sub(1))});
+        }
+
+        let retention_trim = if let Some(retention) = retention {
+            quote! {
+                if let Some(n) = self.#retention.trim_to(self.history.len()) {
+                    self.keep_last(n);
+                }
+            }
+        } else {
+            quote! {}
+        };
+
+        let save_state_body = if keep_conditions.is_empty() {
+            quote! {
+                #self_state_push;
+                #(self.#fields_with_state.save_state();)*
+                #(self.#vecs_with_state.iter_mut().for_each(|x| x.save_state());)*
+            }
+        } else {
+            quote! {
+                #sink_write
+                if #(#keep_conditions)&&* {
+                    #self_state_push;
+                    #(self.#fields_with_state.save_state();)*
+                    #(self.#vecs_with_state.iter_mut().for_each(|x| x.save_state());)*
+                    #retention_trim
+                }
+            }
+        };
+
         impl_block.extend::<TokenStream2>(quote! {
             impl #ident {
                 #save_state_cust_doc
                 pub fn save_state(&mut self) {
-                    #self_state_push;
-                    #(self.#fields_with_state.save_state();)*
+                    #save_state_body
+                }
+
+                /// Keeps only the last `n` elements of `self.history` and of
+                /// histories of all nested fields with state
+                pub fn keep_last(&mut self, n: usize) {
+                    #self_history_keep_last
+                    #(self.#fields_with_state.keep_last(n);)*
+                    #(self.#vecs_with_state.iter_mut().for_each(|x| x.keep_last(n));)*
+                }
+
+                /// Returns names of numeric fields of `self.state` and, prefixed
+                /// with the field name (e.g. `m1.temp`), of all nested fields with
+                /// state, with elements of vecs and arrays prefixed by index as well
+                /// (e.g. `nodes.0.temp`)
+                pub fn state_column_names(&self) -> Vec<String> {
+                    let mut names: Vec<String> = Vec::new();
+                    #self_state_names
+                    #(
+                        names.extend(
+                            self.#fields_with_state
+                                .state_column_names()
+                                .iter()
+                                .map(|name| format!("{}.{}", #fields_with_state_str, name))
+                        );
+                    )*
+                    #(
+                        for (i, x) in self.#vecs_with_state.iter().enumerate() {
+                            names.extend(
+                                x.state_column_names()
+                                    .iter()
+                                    .map(|name| format!("{}.{}.{}", #vecs_with_state_str, i, name))
+                            );
+                        }
+                    )*
+                    names
+                }
+
+                /// Returns history of `self` and all nested fields with state flattened
+                /// into columns named like [Self::state_column_names]
+                pub fn history_columns(&self) -> Columns {
+                    #self_history_columns
+                    #(columns.extend_prefixed(#fields_with_state_str, self.#fields_with_state.history_columns());)*
+                    #(
+                        for (i, x) in self.#vecs_with_state.iter().enumerate() {
+                            columns.extend_prefixed(
+                                &format!("{}.{}", #vecs_with_state_str, i),
+                                x.history_columns(),
+                            );
+                        }
+                    )*
+                    columns
+                }
+
+                /// Returns current values of columns named by [Self::state_column_names]
+                pub fn state_column_values(&self) -> Vec<f64> {
+                    let mut values: Vec<f64> = Vec::new();
+                    #self_state_values
+                    #(values.extend(self.#fields_with_state.state_column_values());)*
+                    #(
+                        for x in self.#vecs_with_state.iter() {
+                            values.extend(x.state_column_values());
+                        }
+                    )*
+                    values
                 }
             }
         });
@@ -1,5 +1,5 @@
 use crate::imports::*;
-use crate::utilities::TokenStreamIterator;
+use crate::utilities::{to_f64_expr, TokenStreamIterator};
 
 pub(crate) fn history_vec_derive(input: TokenStream) -> TokenStream {
     let item_struct: ItemStruct = syn::parse_macro_input!(input as ItemStruct);
@@ -34,6 +34,36 @@ pub(crate) fn history_vec_derive(input: TokenStream) -> TokenStream {
             }
         })
         .concat();
+    // fields that can be represented as `f64` columns
+    let (column_names, column_values): (Vec<String>, Vec<TokenStream2>) = fields
+        .iter()
+        .filter_map(|f| {
+            let ident = f.ident.as_ref().unwrap();
+            to_f64_expr(&f.ty, quote! {self.#ident}).map(|expr| (ident.to_string(), expr))
+        })
+        .unzip();
+    let column_vecs = fields
+        .iter()
+        .filter_map(|f| {
+            let ident = f.ident.as_ref().unwrap();
+            to_f64_expr(&f.ty, quote! {(*x)})
+                .map(|expr| quote! {self.#ident.iter().map(|x| #expr).collect()})
+        })
+        .collect::<Vec<_>>();
+    let has_time = field_names.iter().any(|ident| *ident == "time");
+    let slice_time = if has_time {
+        quote! {
+            /// Returns new history vec with elements whose `time` is within
+            /// `t_start..=t_end`
+            pub fn slice_time(&self, t_start: f64, t_end: f64) -> #new_name {
+                self.iter()
+                    .filter(|x| x.time >= t_start && x.time <= t_end)
+                    .collect()
+            }
+        }
+    } else {
+        quote! {}
+    };
     let mut generated = TokenStream2::new();
     let struct_doc: TokenStream2 = format!("/// Stores history of {}", original_name_str)
         .parse()
@@ -44,6 +74,12 @@ pub(crate) fn history_vec_derive(input: TokenStream) -> TokenStream {
     )
     .parse()
     .unwrap();
+    let get_doc: TokenStream2 = format!(
+        "/// Returns element `i` as {}, or `None` if out of bounds",
+        original_name_str
+    )
+    .parse()
+    .unwrap();
     generated.append_all(quote! {
         #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, PartialOrd)]
         #[pyo3_api(
@@ -51,6 +87,39 @@ pub(crate) fn history_vec_derive(input: TokenStream) -> TokenStream {
             fn len_py(&self) -> usize {
                 self.len()
             }
+
+            fn __len__(&self) -> usize {
+                self.len()
+            }
+
+            /// Returns element `i` with support for negative indexing, or for a
+            /// slice, e.g. `h[2:5]` or `h[::2]`, a history of the selected elements
+            fn __getitem__(&self, py: Python, key: &PyAny) -> PyResult<PyObject> {
+                if let Ok(slice) = key.downcast::<PySlice>() {
+                    let indices = slice.indices(self.len() as std::os::raw::c_long)?;
+                    let (start, step, n) = (indices.start, indices.step, indices.slicelength);
+                    let sliced = if step == 1 {
+                        self.slice(start as usize..(start + n) as usize)
+                    } else {
+                        (0..n)
+                            .filter_map(|k| self.get((start + k * step) as usize))
+                            .collect()
+                    };
+                    return Ok(sliced.into_py(py));
+                }
+                let i: isize = key.extract()?;
+                let idx = if i < 0 { i + self.len() as isize } else { i };
+                usize::try_from(idx)
+                    .ok()
+                    .and_then(|idx| self.get(idx))
+                    .map(|x| x.into_py(py))
+                    .ok_or_else(|| PyIndexError::new_err("index out of range"))
+            }
+
+            fn __iter__(&self, py: Python) -> PyResult<PyObject> {
+                let elements: Vec<PyObject> = self.iter().map(|x| x.into_py(py)).collect();
+                Ok(PyList::new(py, elements).call_method0("__iter__")?.into())
+            }
         )]
         #struct_doc
         pub struct #new_name {
@@ -70,6 +139,35 @@ pub(crate) fn history_vec_derive(input: TokenStream) -> TokenStream {
                 #(self.#field_names.push(value.#field_names);)*
             }
 
+            #get_doc
+            pub fn get(&self, i: usize) -> Option<#original_name> {
+                if i < self.len() {
+                    Some(#original_name{#(#field_names: self.#field_names[i].clone()),*})
+                } else {
+                    None
+                }
+            }
+
+            /// Returns iterator over elements reconstructed from vec fields
+            pub fn iter(&self) -> impl Iterator<Item = #original_name> + '_ {
+                (0..self.len()).map(move |i| self.get(i).unwrap())
+            }
+
+            /// Returns new history vec with elements in index `range`
+            pub fn slice<R: std::ops::RangeBounds<usize>>(&self, range: R) -> #new_name {
+                let bounds = (range.start_bound().cloned(), range.end_bound().cloned());
+                #new_name {
+                    #(#field_names: self.#field_names[bounds].to_vec(),)*
+                }
+            }
+
+            #slice_time
+
+            /// Shortens all vecs to `len` elements, dropping the newest
+            pub fn truncate(&mut self, len: usize) {
+                #(self.#field_names.truncate(len);)*
+            }
+
             /// clear all history vecs
             pub fn clear(&mut self) {
                 #(self.#field_names.clear();)*
@@ -87,17 +185,71 @@ pub(crate) fn history_vec_derive(input: TokenStream) -> TokenStream {
                 }
             }
 
+            /// Keeps only the last `n` elements, dropping the oldest
+            pub fn keep_last(&mut self, n: usize) {
+                if self.len() > n {
+                    let n_drop = self.len() - n;
+                    #(self.#field_names.drain(..n_drop);)*
+                }
+            }
+
             /// Returns len of contained vectors
             pub fn len(&self) -> usize {
                 self.#first_field.len()
             }
 
+            /// Returns numeric fields as named `f64` columns
+            pub fn columns(&self) -> Columns {
+                let mut columns = Columns::new();
+                #(columns.push(#column_names.to_string(), #column_vecs);)*
+                columns
+            }
+
             /// Returns True if contained vecs are empty
             pub fn is_empty(&self) -> bool {
                 self.#first_field.is_empty()
             }
         }
 
+        impl StateColumns for #original_name {
+            fn column_names() -> Vec<String> {
+                vec![#(#column_names.to_string()),*]
+            }
+            fn column_values(&self) -> Vec<f64> {
+                vec![#(#column_values),*]
+            }
+        }
+
+        impl Extend<#original_name> for #new_name {
+            fn extend<I: IntoIterator<Item = #original_name>>(&mut self, iter: I) {
+                for value in iter {
+                    self.push(value);
+                }
+            }
+        }
+
+        impl FromIterator<#original_name> for #new_name {
+            fn from_iter<I: IntoIterator<Item = #original_name>>(iter: I) -> Self {
+                let mut history = #new_name::new();
+                history.extend(iter);
+                history
+            }
+        }
+
+        impl IntoIterator for #new_name {
+            type Item = #original_name;
+            type IntoIter = std::vec::IntoIter<#original_name>;
+
+            fn into_iter(self) -> Self::IntoIter {
+                #(let mut #field_names = self.#field_names.into_iter();)*
+                std::iter::from_fn(move || {
+                    Some(#original_name{#(#field_names: #field_names.next()?),*})
+                })
+                .collect::<Vec<_>>()
+                .into_iter()
+            }
+        }
+
         impl Default for #new_name {
             fn default() -> #new_name {
                 #new_name::new()
@@ -4,4 +4,6 @@ pub(crate) use proc_macro2::TokenStream as TokenStream2;
 pub(crate) use proc_macro_error::{abort, abort_call_site, proc_macro_error, emit_error};
 pub(crate) use quote::{quote, ToTokens, TokenStreamExt}; // ToTokens is implicitly used as a trait
 pub(crate) use regex::Regex;
-pub(crate) use syn::{spanned::Spanned, Field, Ident, ItemFn, ItemStruct, Meta, Type};
+pub(crate) use syn::{
+    spanned::Spanned, Attribute, Field, Fields, Ident, ItemFn, ItemStruct, Meta, NestedMeta, Type,
+};
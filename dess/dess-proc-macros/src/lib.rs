@@ -16,9 +16,23 @@ pub fn history_vec_derive(input: TokenStream) -> TokenStream {
 }
 
 /// Derives `save_state` method for struct and all fields marked with
-/// `save_state` or `use_state` attributes
+/// `save_state`, `use_state`, or `use_flow_state` attributes.  Also registers the `breakpoints`
+/// attribute used by `solver` and the `observers`, `sink`, `retention`, and `snapshot` markers
+/// of fields that enable optional features.
 #[proc_macro_error]
-#[proc_macro_derive(HistoryMethods, attributes(use_state, save_state))]
+#[proc_macro_derive(
+    HistoryMethods,
+    attributes(
+        use_state,
+        use_flow_state,
+        save_state,
+        breakpoints,
+        observers,
+        sink,
+        retention,
+        snapshot
+    )
+)]
 pub fn history_methods_derive(input: TokenStream) -> TokenStream {
     history_methods::history_methods_derive(input)
 }
@@ -32,7 +46,7 @@ pub fn solver(attr: TokenStream, item: TokenStream) -> TokenStream {
 
 /// Derives `bare_clone` method
 #[proc_macro_error]
-#[proc_macro_derive(BareClone, attributes(use_state, save_state))]
+#[proc_macro_derive(BareClone, attributes(use_state, use_flow_state, save_state))]
 pub fn bare_clone(input: TokenStream) -> TokenStream {
     bare_clone::bare_clone_derive(input)
 }
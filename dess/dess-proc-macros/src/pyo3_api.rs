@@ -1,5 +1,5 @@
 use crate::imports::*;
-use crate::utilities::parse_ts_as_fn_defs;
+use crate::utilities::{marked_field, parse_ts_as_fn_defs};
 
 /// Derives several methods for struct
 pub(crate) fn pyo3_api(attr: TokenStream, item: TokenStream) -> TokenStream {
@@ -31,6 +31,109 @@ pub(crate) fn pyo3_api(attr: TokenStream, item: TokenStream) -> TokenStream {
         TokenStream2::new()
     };
 
+    let observers = marked_field(&item_struct.fields, "observers");
+
+    let observers_block = if let Some(observers) = observers {
+        quote! {
+            /// Attaches python callable to be called after every internal solver
+            /// step with arguments `(time, states, derivs, dt, accepted, solver_state)`
+            fn attach_observer(&mut self, callback: PyObject) {
+                self.#observers.attach(PyObserver { callback });
+            }
+
+            /// Detaches all observers
+            fn clear_observers(&mut self) {
+                self.#observers.clear();
+            }
+        }
+    } else {
+        TokenStream2::new()
+    };
+
+    let sink = marked_field(&item_struct.fields, "sink");
+
+    let sink_block = if let Some(sink) = sink {
+        quote! {
+            /// Streams rows of `state_column_names` to CSV file `filename` on every
+            /// `save_state`.  If `keep_history` is false, history vecs are not populated.
+            fn attach_csv_sink(&mut self, filename: &str, keep_history: Option<bool>) -> PyResult<()> {
+                self.#sink.attach(CsvSink::from_path(filename)?, keep_history.unwrap_or(true));
+                Ok(())
+            }
+
+            /// Flushes and detaches history sink
+            fn detach_sink(&mut self) -> PyResult<()> {
+                Ok(self.#sink.detach()?)
+            }
+        }
+    } else {
+        TokenStream2::new()
+    };
+
+    let retention = marked_field(&item_struct.fields, "retention");
+
+    let retention_block = if let Some(retention) = retention {
+        quote! {
+            /// History retention policy as json, e.g. `{"LastN": {"n": 10}}`
+            #[getter]
+            fn get_retention(&self) -> String {
+                self.#retention.to_json()
+            }
+
+            #[setter]
+            fn set_retention(&mut self, retention: &str) -> PyResult<()> {
+                self.#retention = Retention::from_json(retention)?;
+                Ok(())
+            }
+        }
+    } else {
+        TokenStream2::new()
+    };
+
+    let snapshot = marked_field(&item_struct.fields, "snapshot");
+
+    let snapshot_block = if snapshot.is_some() {
+        quote! {
+            /// Restores system to its initial conditions from before the first run
+            #[pyo3(name = "reset")]
+            fn reset_py(&mut self) -> PyResult<()> {
+                Ok(self.reset()?)
+            }
+        }
+    } else {
+        TokenStream2::new()
+    };
+
+    let has_nested_history = item_struct.fields.iter().any(|field| {
+        field
+            .attrs
+            .iter()
+            .any(|attr| attr.path.is_ident("use_state") || attr.path.is_ident("save_state"))
+    });
+
+    let history_columns_block = if has_nested_history {
+        quote! {
+            /// Returns dict of history flattened into named columns,
+            /// e.g. `time`, `m1.temp`, `h12.q`
+            #[pyo3(name = "history_columns")]
+            fn history_columns_py<'py>(&self, py: Python<'py>) -> PyResult<&'py PyDict> {
+                let dict = PyDict::new(py);
+                for (name, values) in self.history_columns().iter() {
+                    dict.set_item(name, values.clone())?;
+                }
+                Ok(dict)
+            }
+
+            /// Writes history flattened into named columns to `filename`.
+            /// Currently `.csv` and `.json` suffixes are supported.
+            fn history_to_file(&self, filename: &str) -> PyResult<()> {
+                Ok(self.history_columns().to_file(filename)?)
+            }
+        }
+    } else {
+        TokenStream2::new()
+    };
+
     let mut pyo3_fns: Vec<TokenStream2> = Vec::new();
 
     let mut fields = item_struct.fields;
@@ -61,6 +164,11 @@ pub(crate) fn pyo3_api(attr: TokenStream, item: TokenStream) -> TokenStream {
             #attr_ts2
             #(#pyo3_fns)*
             #walk_block
+            #observers_block
+            #sink_block
+            #retention_block
+            #snapshot_block
+            #history_columns_block
             #[classmethod]
             #[pyo3(name = "default")]
             /// Exposes `default` to python.
@@ -74,7 +182,7 @@ pub(crate) fn pyo3_api(attr: TokenStream, item: TokenStream) -> TokenStream {
             /// # Argument:
             ///
             /// * `filename`: a `str` storing the targeted file name. Currently `.json` and `.yaml` suffixes are
-            /// supported
+            ///   supported
             ///
             /// # Returns:
             ///
@@ -91,7 +199,7 @@ pub(crate) fn pyo3_api(attr: TokenStream, item: TokenStream) -> TokenStream {
             /// # Argument:
             ///
             /// * `filename`: a `str` storing the targeted file name. Currently `.json` and `.yaml` suffixes are
-            /// supported
+            ///   supported
             ///
             /// # Returns:
             ///
@@ -1,5 +1,5 @@
 use crate::imports::*;
-use crate::utilities::parse_ts_as_fn_defs;
+use crate::utilities::{has_serde_skip, is_vec_or_array, marked_field, parse_ts_as_fn_defs};
 
 /// Derives several methods for struct
 pub(crate) fn solver_attr(attr: TokenStream, item: TokenStream) -> TokenStream {
@@ -35,6 +35,197 @@ pub(crate) fn solver_attr(attr: TokenStream, item: TokenStream) -> TokenStream {
         .map(|(f, _hsv)| f.ident.as_ref().unwrap())
         .collect::<Vec<_>>();
 
+    let fields_with_flow_state = fields
+        .iter()
+        .filter(|field| {
+            field
+                .attrs
+                .iter()
+                .any(|attr| attr.path.is_ident("use_flow_state"))
+        })
+        .map(|f| {
+            if is_vec_or_array(f) {
+                abort!(
+                    f.ty.span(),
+                    "`use_flow_state` is not supported on Vec or array fields; \
+                    use one field per flow state"
+                );
+            }
+            f.ident.as_ref().unwrap()
+        })
+        .collect::<Vec<_>>();
+
+    let fields_with_state_str = fields_with_state
+        .iter()
+        .map(|x| x.to_string())
+        .collect::<Vec<_>>();
+    let fields_with_flow_state_str = fields_with_flow_state
+        .iter()
+        .map(|x| x.to_string())
+        .collect::<Vec<_>>();
+
+    let fields_with_breakpoints = fields
+        .iter()
+        .filter(|field| {
+            field
+                .attrs
+                .iter()
+                .any(|attr| attr.path.is_ident("breakpoints"))
+        })
+        .map(|f| f.ident.as_ref().unwrap())
+        .collect::<Vec<_>>();
+
+    let breakpoints_block = if fields_with_breakpoints.is_empty() {
+        TokenStream2::new()
+    } else {
+        quote! {
+            fn next_breakpoint(&self) -> Option<f64> {
+                [#(HasBreakpoints::next_breakpoint(&self.#fields_with_breakpoints, self.state.time)),*]
+                    .into_iter()
+                    .flatten()
+                    .reduce(f64::min)
+            }
+        }
+    };
+
+    let observers = marked_field(fields, "observers");
+
+    let observers_block = if let Some(observers) = observers {
+        quote! {
+            fn observers(&self) -> Option<&Observers> {
+                Some(&self.#observers)
+            }
+        }
+    } else {
+        TokenStream2::new()
+    };
+
+    let sink = marked_field(fields, "sink");
+
+    let retention = marked_field(fields, "retention");
+
+    let (retention_block, retention_trim_block, retention_trim) = if let Some(retention) = retention {
+        (
+            quote! {
+                fn retention(&self) -> Retention {
+                    self.#retention
+                }
+            },
+            quote! {
+                /// Trims histories, including that of an adaptive solver, to the
+                /// length set by `retention`, which `save_state` only does in batches
+                pub fn trim_history(&mut self) {
+                    if let Some(n) = self.#retention.max_len() {
+                        self.keep_last(n);
+                        if let Some(sc) = self.sc_mut() {
+                            sc.history.keep_last(n);
+                        }
+                    }
+                }
+            },
+            quote! {
+                self.trim_history();
+            },
+        )
+    } else {
+        (TokenStream2::new(), TokenStream2::new(), TokenStream2::new())
+    };
+
+    let snapshot = marked_field(fields, "snapshot");
+
+    let snapshot_take = if let Some(snapshot) = snapshot {
+        quote! {
+            if self.state.i == 0 && !self.#snapshot.is_taken() {
+                self.#snapshot = Snapshot::of(&*self);
+            }
+        }
+    } else {
+        TokenStream2::new()
+    };
+
+    // fields skipped by serde, e.g. observers, sinks, and the snapshot itself,
+    // are not part of the snapshot and so are kept by `reset`
+    let runtime_fields = fields
+        .iter()
+        .filter(|x| has_serde_skip(&x.attrs))
+        .map(|x| x.ident.as_ref().unwrap())
+        .collect::<Vec<_>>();
+
+    let (checkpoint_encode, resume_decode) = if let Some(snapshot) = snapshot {
+        (
+            quote! {
+                (self.to_bincode(), self.#snapshot.encoded()).to_bincode()
+            },
+            quote! {
+                let (encoded, snapshot) =
+                    <(Vec<u8>, Option<Vec<u8>>)>::from_bincode(&std::fs::read(path)?)?;
+                let mut sys = Self::from_bincode(&encoded)?;
+                sys.#snapshot = Snapshot::from_encoded(snapshot);
+                Ok(sys)
+            },
+        )
+    } else {
+        (
+            quote! {
+                self.to_bincode()
+            },
+            quote! {
+                Self::from_bincode(&std::fs::read(path)?)
+            },
+        )
+    };
+
+    let reset_block = if let Some(snapshot) = snapshot {
+        quote! {
+            /// Restores system to the snapshot taken when a run was first started
+            /// from `state.i == 0`, keeping fields skipped by serde, like attached
+            /// observers and sink
+            pub fn reset(&mut self) -> dess::anyhow::Result<()> {
+                let mut initial: Self = self.#snapshot.restore()?;
+                #(std::mem::swap(&mut initial.#runtime_fields, &mut self.#runtime_fields);)*
+                *self = initial;
+                Ok(())
+            }
+            /// Resets system, applies `params`, e.g. `|sys| sys.m1.c = 2.0`, and walks.
+            /// The snapshot is unchanged, so a later [Self::reset] discards `params`.
+            pub fn rerun_with<F: FnOnce(&mut Self)>(&mut self, params: F) -> dess::anyhow::Result<()> {
+                self.reset()?;
+                params(self);
+                self.walk();
+                Ok(())
+            }
+        }
+    } else {
+        TokenStream2::new()
+    };
+
+    let (sink_flush, sink_check, sink_flush_result) = if let Some(sink) = sink {
+        (
+            quote! {
+                // a failed sink keeps its error for `Sink::flush` and `Sink::detach`
+                let _ = self.#sink.flush();
+            },
+            quote! {
+                self.#sink.check()?;
+            },
+            quote! {
+                self.#sink.flush()?;
+            },
+        )
+    } else {
+        (TokenStream2::new(), TokenStream2::new(), TokenStream2::new())
+    };
+
+    let simulate_sink = if let Some(sink) = sink {
+        quote! {
+            fn sink_mut(&mut self) -> Option<&mut Sink> {
+                Some(&mut self.#sink)
+            }
+        }
+    } else {
+        TokenStream2::new()
+    };
+
     let mut item_and_impl_block = TokenStream2::default();
 
     item_and_impl_block.extend::<TokenStream2>(item_struct.to_token_stream());
@@ -43,59 +234,103 @@ pub(crate) fn solver_attr(attr: TokenStream, item: TokenStream) -> TokenStream {
             /// returns values of states
             fn states(&self) -> Vec<f64> {
                 let mut states: Vec<f64> = Vec::new();
-                #(states.push(self.#fields_with_state.state());)*
+                #(states.extend(HasStateSlice::states(&self.#fields_with_state));)*
+                #(states.push(self.#fields_with_flow_state.flow());)*
                 states
             }
             /// sets values of states
             fn set_states(&mut self, val: Vec<f64>) {
-                let mut iter = val.iter();
-                #(self.#fields_with_state.set_state(iter.next().unwrap().clone());)*
+                let mut val = &val[..];
+                #(
+                    let n = HasStateSlice::n_states(&self.#fields_with_state);
+                    HasStateSlice::set_states(&mut self.#fields_with_state, split_states(&mut val, n));
+                )*
+                #(self.#fields_with_flow_state.set_flow_state(split_states(&mut val, 1)[0]);)*
             }
             /// assuming `set_derivs` has been called, steps
             /// value of states by deriv * dt
             fn step_states_by_dt(&mut self, dt: &f64) {
-                #(self.#fields_with_state.step_state_by_dt(dt);)*
+                #(HasStateSlice::step_states_by_dt(&mut self.#fields_with_state, dt);)*
+                #(self.#fields_with_flow_state.step_flow_state_by_dt(dt);)*
                 self.step_time(dt);
             }
             /// assuming `set_derivs` has been called, steps
             /// value of states by deriv * dt
             fn step_states(&mut self, val: Vec<f64>) {
-                let mut iter = val.iter();
-                #(self.#fields_with_state.step_state(iter.next().unwrap().clone());)*
+                let mut val = &val[..];
+                #(
+                    let n = HasStateSlice::n_states(&self.#fields_with_state);
+                    HasStateSlice::step_states(&mut self.#fields_with_state, split_states(&mut val, n));
+                )*
+                #(self.#fields_with_flow_state.step_flow_state(split_states(&mut val, 1)[0]);)*
             }
             /// returns derivatives of states
             fn derivs(&self) -> Vec<f64> {
                 let mut derivs: Vec<f64> = Vec::new();
-                #(derivs.push(self.#fields_with_state.deriv());)*
+                #(derivs.extend(HasStateSlice::derivs(&self.#fields_with_state));)*
+                #(derivs.push(self.#fields_with_flow_state.flow_deriv());)*
                 derivs
             }
             /// sets values of derivatives of states
             fn set_derivs(&mut self, val: &[f64]) {
-                let mut iter = val.iter();
-                #(self.#fields_with_state.set_deriv(iter.next().unwrap().clone());)*
+                let mut val = val;
+                #(
+                    let n = HasStateSlice::n_states(&self.#fields_with_state);
+                    HasStateSlice::set_derivs(&mut self.#fields_with_state, split_states(&mut val, n));
+                )*
+                #(self.#fields_with_flow_state.set_flow_deriv(split_states(&mut val, 1)[0]);)*
             }
             /// steps derivs by val
             fn step_derivs(&mut self, val: Vec<f64>) {
-                let mut iter = val.iter();
-                #(self.#fields_with_state.step_deriv(iter.next().unwrap().clone());)*
+                let mut val = &val[..];
+                #(
+                    let n = HasStateSlice::n_states(&self.#fields_with_state);
+                    HasStateSlice::step_derivs(&mut self.#fields_with_state, split_states(&mut val, n));
+                )*
+                #(self.#fields_with_flow_state.step_flow_deriv(split_states(&mut val, 1)[0]);)*
             }
             /// returns value of storage variable (e.g. thermal capacitance \[J/K\])
             fn storages(&self) -> Vec<f64> {
                 let mut storages: Vec<f64> = Vec::new();
-                #(storages.push(self.#fields_with_state.storage());)*
+                #(storages.extend(HasStateSlice::storages(&self.#fields_with_state));)*
+                #(storages.push(self.#fields_with_flow_state.inertance());)*
                 storages
             }
+            /// returns names of states: fields with `use_state`, suffixed with
+            /// e.g. `.0` for each state of a [HasStateSlice] with more than one,
+            /// followed by fields with `use_flow_state`
+            fn state_names(&self) -> Vec<String> {
+                let mut names: Vec<String> = Vec::new();
+                #(
+                    names.extend(
+                        HasStateSlice::state_labels(&self.#fields_with_state)
+                            .into_iter()
+                            .map(|label| if label.is_empty() {
+                                #fields_with_state_str.to_string()
+                            } else {
+                                format!("{}.{}", #fields_with_state_str, label)
+                            })
+                    );
+                )*
+                #(names.push(#fields_with_flow_state_str.to_string());)*
+                names
+            }
         }
 
         impl SolverBase for #ident {
             /// reset all time derivatives to zero for start of `solve_step`
             fn reset_derivs(&mut self) {
-                #(self.#fields_with_state.set_deriv(0.0);)*
+                #(HasStateSlice::reset_derivs(&mut self.#fields_with_state);)*
+                #(self.#fields_with_flow_state.set_flow_deriv(0.0);)*
             }
             /// steps dt without affecting states
             fn step_time(&mut self, dt: &f64) {
                 self.state.time += dt;
             }
+            /// sets time without affecting states
+            fn set_time(&mut self, t: f64) {
+                self.state.time = t;
+            }
             fn sc(&self) -> Option<&AdaptiveSolverConfig> {
                 match &self.solver_type {
                     SolverTypes::RK45CashKarp(sc) => Some(sc),
@@ -113,54 +348,119 @@ pub(crate) fn solver_attr(attr: TokenStream, item: TokenStream) -> TokenStream {
             fn state(&self) -> &dess::SystemState {
                 &self.state
             }
+            fn solver_type(&self) -> &SolverTypes {
+                &self.solver_type
+            }
+            fn t_report(&self) -> &[f64] {
+                &self.t_report
+            }
+            #retention_block
+            #breakpoints_block
+            #observers_block
             #fn_from_attr
         }
 
         impl SolverVariantMethods for #ident{}
 
+        impl Simulate for #ident {
+            fn walk(&mut self) {
+                #ident::walk(self)
+            }
+            fn history_columns(&self) -> Columns {
+                #ident::history_columns(self)
+            }
+            #simulate_sink
+        }
+
         impl #ident {
             /// iterates through time until last value of `t_report`
             pub fn walk(&mut self) {
+                #snapshot_take
+                while &self.state.time < self.t_report.last().unwrap() {
+                    self.solve_step();
+                    self.state.i += 1;
+                    self.save_state();
+                }
+                #retention_trim
+                #sink_flush
+            }
+            /// Like [Self::walk] but also writes a [Self::checkpoint] to `path`
+            /// after every `every` saved states.  Stops with the error of an
+            /// attached history sink, if it fails.
+            pub fn walk_with_checkpoints<P: AsRef<std::path::Path>>(
+                &mut self,
+                path: P,
+                every: usize,
+            ) -> dess::anyhow::Result<()> {
+                #snapshot_take
                 while &self.state.time < self.t_report.last().unwrap() {
                     self.solve_step();
                     self.state.i += 1;
                     self.save_state();
+                    #sink_check
+                    if self.state.i % every.max(1) == 0 {
+                        self.checkpoint(&path)?;
+                    }
                 }
+                #retention_trim
+                #sink_flush_result
+                Ok(())
+            }
+            /// Writes complete system, including solver state, histories,
+            /// `state.i`, and any snapshot, as bincode to `path`.  Writes to a temporary file first
+            /// so that an interrupted write leaves any previous checkpoint intact.
+            pub fn checkpoint<P: AsRef<std::path::Path>>(&self, path: P) -> dess::anyhow::Result<()> {
+                let path = path.as_ref();
+                let tmp_path = path.with_extension("tmp");
+                std::fs::write(&tmp_path, #checkpoint_encode)?;
+                std::fs::rename(&tmp_path, path)?;
+                Ok(())
+            }
+            /// Reads system written by [Self::checkpoint], e.g. to continue with
+            /// [Self::walk].  Fields skipped by serde, like attached observers
+            /// and sinks, must be reattached.
+            pub fn resume<P: AsRef<std::path::Path>>(path: P) -> dess::anyhow::Result<Self> {
+                #resume_decode
             }
-            /// Runs `solver_type` specific step method that calls
-            /// [Self::step] in solver-specific manner
-            pub fn solve_step(&mut self) {
-                while self.state.time < self.t_report[self.state.i] {
-                    let dt = self.t_report[self.state.i] - self.state.time;
-                    match &self.solver_type {
-                        SolverTypes::EulerFixed{dt: dt_fixed} => {
-                            let dt = dt.min(dt_fixed.clone());
-                            self.euler(&dt);
-                        },
-                        SolverTypes::HeunsMethod{dt: dt_fixed} => {
-                            let dt = dt.min(dt_fixed.clone());
-                            self.heun(&dt);
-                        },
-                        SolverTypes::MidpointMethod{dt: dt_fixed} => {
-                            let dt = dt.min(dt_fixed.clone());
-                            self.midpoint(&dt);
-                        },
-                        SolverTypes::RalstonsMethod{dt: dt_fixed} => {
-                            let dt = dt.min(dt_fixed.clone());
-                            self.ralston(&dt);
-                        },
-                        SolverTypes::RK23BogackiShampine(_sc) => {
-                            let dt = self.rk23_bogacki_shampine(&dt);
-                        },
-                        SolverTypes::RK4Fixed{dt: dt_fixed} => {
-                            let dt = dt.min(dt_fixed.clone());
-                            self.rk4fixed(&dt);
-                        },
-                        SolverTypes::RK45CashKarp(_sc) => {
-                            let dt = self.rk45_cash_karp(&dt);
-                        },
+            #reset_block
+            #retention_trim_block
+            /// Advances system to time `t` without using `t_report` and saves
+            /// state once `t` is reached, or does nothing if it has been.
+            /// Adaptive solvers keep the `dt` they proposed before a step that
+            /// was shortened to land on `t`, unless error control asks for less.
+            pub fn advance_to(&mut self, t: f64) {
+                #snapshot_take
+                if self.state.i == 0 {
+                    // save initial state, as `walk` does for `t_report[0]`
+                    self.state.i += 1;
+                    self.save_state();
+                }
+                if self.state.time < t {
+                    while self.state.time < t {
+                        let dt_remaining = t - self.state.time;
+                        let dt_proposed = self.sc().map(|sc| sc.state.dt);
+                        self.step_toward(t);
+                        match dt_proposed {
+                            Some(dt_proposed) if dt_proposed > dt_remaining && self.state.time >= t => {
+                                // the proposal that follows a shortened step is based on
+                                // that step, so it is only kept if smaller than the step
+                                let sc = self.sc_mut().unwrap();
+                                if sc.state.dt >= dt_remaining {
+                                    sc.state.dt = dt_proposed;
+                                }
+                            }
+                            _ => {}
+                        }
                     }
+                    self.state.i += 1;
+                    self.save_state();
                 }
+                #retention_trim
+                #sink_flush
+            }
+            /// Advances system by `dt` -- see [Self::advance_to]
+            pub fn advance_by(&mut self, dt: f64) {
+                self.advance_to(self.state.time + dt);
             }
         }
     });
@@ -25,7 +25,6 @@ impl<T: Iterator<Item = proc_macro2::TokenStream>> TokenStreamIterator for T {
     }
 }
 
-#[allow(unused)]
 /// Checks if a field is a vec
 pub fn is_vec(field: &Field) -> bool {
     if let Type::Path(type_path) = &field.ty {
@@ -38,6 +37,137 @@ pub fn is_vec(field: &Field) -> bool {
     false
 }
 
+/// Checks if a field is a fixed-size array
+pub fn is_array(field: &Field) -> bool {
+    matches!(field.ty, Type::Array(_))
+}
+
+/// Checks if a field is a vec or array of e.g. components, whose elements
+/// are handled individually
+pub fn is_vec_or_array(field: &Field) -> bool {
+    is_vec(field) || is_array(field)
+}
+
+/// Markers of fields that enable optional features of a `solver` system,
+/// e.g. `#[sink]`, and the types of the fields they go on
+const FEATURE_MARKERS: [(&str, &str); 4] = [
+    ("observers", "Observers"),
+    ("sink", "Sink"),
+    ("retention", "Retention"),
+    ("snapshot", "Snapshot"),
+];
+
+/// Returns last segment of a type's path, e.g. `Sink` for `dess::sink::Sink`
+fn type_name(ty: &Type) -> Option<String> {
+    match ty {
+        Type::Path(type_path) => type_path
+            .path
+            .segments
+            .last()
+            .map(|segment| segment.ident.to_string()),
+        _ => None,
+    }
+}
+
+/// Returns ident of the field marked with feature marker `marker`, e.g.
+/// `#[sink]`, if any.  Aborts if the marker is duplicated, takes arguments,
+/// or is on a field of another type, or if a field of the marker's type is
+/// not marked, so that a feature is never silently left disabled.
+pub fn marked_field<'a>(fields: &'a Fields, marker: &str) -> Option<&'a Ident> {
+    let (_, ty) = FEATURE_MARKERS
+        .iter()
+        .find(|(m, _)| *m == marker)
+        .expect("unknown feature marker");
+    let mut marked = None;
+    for field in fields {
+        let attrs = field
+            .attrs
+            .iter()
+            .filter(|attr| attr.path.is_ident(marker))
+            .collect::<Vec<_>>();
+        if let Some(attr) = attrs.iter().find(|attr| !attr.tokens.is_empty()) {
+            abort!(attr.span(), "`#[{}]` takes no arguments", marker);
+        }
+        if attrs.len() > 1 || (!attrs.is_empty() && marked.is_some()) {
+            abort!(
+                attrs.last().unwrap().span(),
+                "duplicate `#[{}]` marker",
+                marker
+            );
+        }
+        let is_marker_type = type_name(&field.ty).as_deref() == Some(*ty);
+        match (attrs.is_empty(), is_marker_type) {
+            (false, true) => marked = field.ident.as_ref(),
+            (false, false) => abort!(
+                field.ty.span(),
+                "`#[{}]` must be on a field of type `{}`",
+                marker,
+                ty
+            ),
+            (true, true) => abort!(
+                field.span(),
+                "field of type `{}` must be marked `#[{}]`",
+                ty,
+                marker
+            ),
+            (true, false) => {}
+        }
+    }
+    marked
+}
+
+/// Checks if a field has `#[serde(skip)]`, i.e. is neither serialized nor
+/// deserialized
+pub fn has_serde_skip(attrs: &[Attribute]) -> bool {
+    attrs.iter().any(|attr| {
+        attr.path.is_ident("serde")
+            && matches!(attr.parse_meta(), Ok(Meta::List(list)) if list.nested.iter().any(
+                |nested| matches!(nested, NestedMeta::Meta(Meta::Path(path)) if path.is_ident("skip"))
+            ))
+    })
+}
+
+const NUMERIC_TYPES: [&str; 14] = [
+    "f64", "f32", "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64", "i128",
+    "isize",
+];
+
+/// Returns true if `ty` is a primitive numeric type
+fn is_numeric_type(ty: &Type) -> bool {
+    if let Type::Path(type_path) = ty {
+        if let Some(ident) = type_path.path.get_ident() {
+            return NUMERIC_TYPES.contains(&ident.to_string().as_str());
+        }
+    }
+    false
+}
+
+/// If a value of type `ty` can be represented as an `f64`, returns expression
+/// converting `value` to `f64`.  Primitive numbers are cast and `Option`s of
+/// primitive numbers become `f64::NAN` when `None`.
+/// Returns `None` for all other types (e.g. `Vec`).
+pub fn to_f64_expr(ty: &Type, value: TokenStream2) -> Option<TokenStream2> {
+    if is_numeric_type(ty) {
+        return Some(quote! { #value as f64 });
+    }
+    if let Type::Path(type_path) = ty {
+        if let Some(segment) = type_path.path.segments.last() {
+            if segment.ident == "Option" {
+                if let syn::PathArguments::AngleBracketed(args) = &segment.arguments {
+                    if let Some(syn::GenericArgument::Type(inner)) = args.args.first() {
+                        if is_numeric_type(inner) {
+                            return Some(quote! {
+                                #value.map(|x| x as f64).unwrap_or(f64::NAN)
+                            });
+                        }
+                    }
+                }
+            }
+        }
+    }
+    None
+}
+
 const ONLY_FN_MSG: &str = "Only function definitions allowed here.";
 
 /// accepts `attr` TokenStream from attribute-like proc macro and returns
@@ -0,0 +1,47 @@
+use crate::imports::*;
+use arrow_array::{ArrayRef, Float64Array, RecordBatch};
+use arrow_schema::{DataType, Field, Schema};
+use std::fs::File;
+use std::sync::Arc;
+// re-exported so downstream crates can use matching versions
+pub use arrow_array;
+pub use arrow_ipc;
+pub use arrow_schema;
+pub use parquet;
+
+impl Columns {
+    /// Converts columns to an Arrow [RecordBatch] with one non-nullable
+    /// `Float64` array per column.  Fails if columns differ in length.
+    pub fn to_record_batch(&self) -> anyhow::Result<RecordBatch> {
+        let fields: Vec<Field> = self
+            .names()
+            .iter()
+            .map(|name| Field::new(name, DataType::Float64, false))
+            .collect();
+        let arrays: Vec<ArrayRef> = self
+            .iter()
+            .map(|(_name, values)| Arc::new(Float64Array::from(values.clone())) as ArrayRef)
+            .collect();
+        Ok(RecordBatch::try_new(Arc::new(Schema::new(fields)), arrays)?)
+    }
+
+    /// Writes columns to Parquet file `filename`
+    pub fn to_parquet(&self, filename: &str) -> anyhow::Result<()> {
+        let batch = self.to_record_batch()?;
+        let mut writer =
+            parquet::arrow::ArrowWriter::try_new(File::create(filename)?, batch.schema(), None)?;
+        writer.write(&batch)?;
+        writer.close()?;
+        Ok(())
+    }
+
+    /// Writes columns to Arrow IPC (a.k.a. Feather v2) file `filename`
+    pub fn to_arrow_ipc(&self, filename: &str) -> anyhow::Result<()> {
+        let batch = self.to_record_batch()?;
+        let mut writer =
+            arrow_ipc::writer::FileWriter::try_new(File::create(filename)?, &batch.schema())?;
+        writer.write(&batch)?;
+        writer.finish()?;
+        Ok(())
+    }
+}
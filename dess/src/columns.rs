@@ -0,0 +1,152 @@
+use crate::imports::*;
+use serde::ser::SerializeMap;
+use std::ffi::OsStr;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+/// Ordered collection of named columns, e.g. the flattened history of a
+/// system with columns `time`, `m1.temp`, `m1.dtemp`, `h12.q`, ...
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Columns {
+    names: Vec<String>,
+    values: Vec<Vec<f64>>,
+}
+
+impl Columns {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends column `name` with `values`
+    pub fn push(&mut self, name: String, values: Vec<f64>) {
+        self.names.push(name);
+        self.values.push(values);
+    }
+
+    /// Appends all columns of `other` with names prefixed by `prefix` and `.`
+    pub fn extend_prefixed(&mut self, prefix: &str, other: Columns) {
+        for (name, values) in other.names.into_iter().zip(other.values) {
+            self.push(format!("{}.{}", prefix, name), values);
+        }
+    }
+
+    /// Returns values of column `name`, if it exists
+    pub fn get(&self, name: &str) -> Option<&[f64]> {
+        self.names
+            .iter()
+            .position(|n| n == name)
+            .map(|i| self.values[i].as_slice())
+    }
+
+    /// Returns column names
+    pub fn names(&self) -> &[String] {
+        &self.names
+    }
+
+    /// Returns iterator over `(name, values)` pairs
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &Vec<f64>)> {
+        self.names.iter().zip(self.values.iter())
+    }
+
+    /// Returns number of columns
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    /// Returns true if there are no columns
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    /// Returns number of rows, i.e. length of longest column
+    pub fn n_rows(&self) -> usize {
+        self.values.iter().map(|v| v.len()).max().unwrap_or(0)
+    }
+
+    /// Writes columns as CSV with header row to `writer`.
+    /// Columns shorter than [Self::n_rows] leave empty cells.
+    pub fn to_csv_writer<W: Write>(&self, mut writer: W) -> anyhow::Result<()> {
+        writeln!(writer, "{}", self.names.join(","))?;
+        for i in 0..self.n_rows() {
+            let row: Vec<String> = self
+                .values
+                .iter()
+                .map(|v| v.get(i).map(|x| x.to_string()).unwrap_or_default())
+                .collect();
+            writeln!(writer, "{}", row.join(","))?;
+        }
+        Ok(())
+    }
+
+    /// Returns columns as CSV string with header row
+    pub fn to_csv(&self) -> String {
+        let mut buf: Vec<u8> = Vec::new();
+        self.to_csv_writer(&mut buf).unwrap();
+        String::from_utf8(buf).unwrap()
+    }
+
+    /// Returns columns as JSON object mapping name to values, preserving order
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(&self).unwrap()
+    }
+
+    /// Save columns to file.  Method adaptively writes `.csv` or `.json`
+    /// dependent on the suffix of `filename`.  With the `arrow` feature,
+    /// `.parquet` and `.arrow`/`.feather` are also supported.
+    pub fn to_file(&self, filename: &str) -> anyhow::Result<()> {
+        let extension = Path::new(filename)
+            .extension()
+            .and_then(OsStr::to_str)
+            .unwrap_or("");
+        match extension {
+            "csv" => {
+                let mut writer = BufWriter::new(File::create(filename)?);
+                self.to_csv_writer(&mut writer)?;
+                writer.flush()?;
+                Ok(())
+            }
+            "json" => {
+                serde_json::to_writer(&File::create(filename)?, self)?;
+                Ok(())
+            }
+            #[cfg(feature = "arrow")]
+            "parquet" => self.to_parquet(filename),
+            #[cfg(feature = "arrow")]
+            "arrow" | "feather" => self.to_arrow_ipc(filename),
+            _ => Err(anyhow!("Unsupported file extension {}", extension)),
+        }
+    }
+}
+
+impl Serialize for Columns {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.len()))?;
+        for (name, values) in self.iter() {
+            map.serialize_entry(name, values)?;
+        }
+        map.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_columns() {
+        let mut columns = Columns::new();
+        columns.push("time".into(), vec![0.0, 0.5]);
+        let mut m1 = Columns::new();
+        m1.push("temp".into(), vec![1.0]);
+        columns.extend_prefixed("m1", m1);
+        assert_eq!(columns.names(), ["time", "m1.temp"]);
+        assert_eq!(columns.get("m1.temp"), Some([1.0].as_slice()));
+        assert!(columns.get("temp").is_none());
+        assert_eq!((columns.len(), columns.n_rows()), (2, 2));
+        // shorter columns leave empty cells
+        assert_eq!(columns.to_csv(), "time,m1.temp\n0,1\n0.5,\n");
+        assert_eq!(columns.to_json(), "{\"time\":[0.0,0.5],\"m1.temp\":[1.0]}");
+        assert!(columns.to_file("columns.txt").is_err());
+    }
+}
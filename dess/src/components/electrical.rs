@@ -0,0 +1,238 @@
+use crate::imports::*;
+
+/// Capacitor component with capacitance, state, and history
+#[derive(HistoryMethods, BareClone, Default)]
+#[common_derives]
+#[pyo3_api(
+    #[new]
+    /// New capacitor with capacitance `c` and initial voltage `v0`
+    pub fn __new__(c: f64, v0: f64) -> Self {
+        Self {
+            c,
+            state: CapacitorState {
+                v: v0,
+                dv: Default::default(),
+            },
+            history: Default::default(),
+        }
+    }
+)]
+pub struct Capacitor {
+    /// capacitance \[F\]
+    pub c: f64,
+    #[serde(default)]
+    pub state: CapacitorState,
+    #[serde(default)]
+    pub history: CapacitorStateHistoryVec,
+}
+
+impl HasState for Capacitor {
+    fn set_state(&mut self, val: f64) {
+        self.state.v = val;
+    }
+    fn state(&self) -> f64 {
+        self.state.v
+    }
+    fn deriv(&self) -> f64 {
+        self.state.dv
+    }
+    fn set_deriv(&mut self, val: f64) {
+        self.state.dv = val;
+    }
+    fn step_deriv(&mut self, val: f64) {
+        self.state.dv += val;
+    }
+    fn storage(&self) -> f64 {
+        self.c
+    }
+}
+
+/// Ideal voltage source, i.e. a node whose voltage is prescribed rather
+/// than solved for.  A source at 0 V serves as ground.
+#[derive(HistoryMethods, BareClone, Default)]
+#[common_derives]
+#[pyo3_api(
+    #[new]
+    /// New voltage source with voltage `v0`
+    pub fn __new__(v0: f64) -> Self {
+        Self {
+            state: CapacitorState {
+                v: v0,
+                dv: Default::default(),
+            },
+            history: Default::default(),
+        }
+    }
+)]
+pub struct VoltageSource {
+    #[serde(default)]
+    pub state: CapacitorState,
+    #[serde(default)]
+    pub history: CapacitorStateHistoryVec,
+}
+
+impl HasState for VoltageSource {
+    fn set_state(&mut self, val: f64) {
+        self.state.v = val;
+    }
+    fn state(&self) -> f64 {
+        self.state.v
+    }
+    fn deriv(&self) -> f64 {
+        self.state.dv
+    }
+    fn set_deriv(&mut self, _val: f64) {
+        self.state.dv = 0.0;
+    }
+    fn step_deriv(&mut self, _val: f64) {
+        self.state.dv = 0.0;
+    }
+    fn storage(&self) -> f64 {
+        f64::INFINITY
+    }
+}
+
+/// State for tracking voltage of [Capacitor] and [VoltageSource]
+#[derive(Copy, HistoryVec, Default)]
+#[common_derives]
+#[pyo3_api]
+pub struct CapacitorState {
+    /// voltage \[V\]
+    pub v: f64,
+    /// derivative of voltage w.r.t. time \[V/s\]
+    pub dv: f64,
+}
+
+/// Resistor component
+#[derive(HistoryMethods, BareClone, Default)]
+#[common_derives]
+#[pyo3_api(
+    #[new]
+    fn __new__(r: f64) -> Self {
+        Self {
+            r,
+            state: Default::default(),
+            history: Default::default(),
+        }
+    }
+)]
+pub struct Resistor {
+    /// resistance \[Ω\] between two voltages
+    pub r: f64,
+    #[serde(default)]
+    pub state: ResistorState,
+    #[serde(default)]
+    pub history: ResistorStateHistoryVec,
+}
+
+impl Flow for Resistor {
+    fn flow(&self) -> f64 {
+        self.state.i
+    }
+    fn set_flow(&mut self, p0: &dyn HasState, p1: &dyn HasState) {
+        self.state.i = (p0.state() - p1.state()) / self.r;
+    }
+}
+
+/// Ideal current source that drives current `i` from the first to the
+/// second node it connects, regardless of their voltages
+#[derive(HistoryMethods, BareClone, Default)]
+#[common_derives]
+#[pyo3_api(
+    #[new]
+    fn __new__(i: f64) -> Self {
+        Self {
+            i,
+            state: Default::default(),
+            history: Default::default(),
+        }
+    }
+)]
+pub struct CurrentSource {
+    /// current \[A\]
+    pub i: f64,
+    #[serde(default)]
+    pub state: ResistorState,
+    #[serde(default)]
+    pub history: ResistorStateHistoryVec,
+}
+
+impl Flow for CurrentSource {
+    fn flow(&self) -> f64 {
+        self.state.i
+    }
+    fn set_flow(&mut self, _p0: &dyn HasState, _p1: &dyn HasState) {
+        self.state.i = self.i;
+    }
+}
+
+/// Inductor component, whose current is a state that changes at a rate of
+/// the voltage across it divided by its inductance
+#[derive(HistoryMethods, BareClone, Default)]
+#[common_derives]
+#[pyo3_api(
+    #[new]
+    /// New inductor with inductance `l` and initial current `i0`
+    fn __new__(l: f64, i0: f64) -> Self {
+        Self {
+            l,
+            state: InductorState {
+                i: i0,
+                di: Default::default(),
+            },
+            history: Default::default(),
+        }
+    }
+)]
+pub struct Inductor {
+    /// inductance \[H\]
+    pub l: f64,
+    #[serde(default)]
+    pub state: InductorState,
+    #[serde(default)]
+    pub history: InductorStateHistoryVec,
+}
+
+impl Flow for Inductor {
+    fn flow(&self) -> f64 {
+        self.state.i
+    }
+    fn set_flow(&mut self, p0: &dyn HasState, p1: &dyn HasState) {
+        self.state.di = (p0.state() - p1.state()) / self.l;
+    }
+}
+
+impl HasFlowState for Inductor {
+    fn set_flow_state(&mut self, val: f64) {
+        self.state.i = val;
+    }
+    fn flow_deriv(&self) -> f64 {
+        self.state.di
+    }
+    fn set_flow_deriv(&mut self, val: f64) {
+        self.state.di = val;
+    }
+    fn inertance(&self) -> f64 {
+        self.l
+    }
+}
+
+/// State for tracking current of [Inductor]
+#[derive(Copy, HistoryVec, Default)]
+#[common_derives]
+#[pyo3_api]
+pub struct InductorState {
+    /// current \[A\]
+    pub i: f64,
+    /// derivative of current w.r.t. time \[A/s\]
+    pub di: f64,
+}
+
+/// Struct for tracking flow variables in [Resistor] and [CurrentSource]
+#[derive(Copy, HistoryVec, Default)]
+#[common_derives]
+#[pyo3_api]
+pub struct ResistorState {
+    /// current \[A\]
+    pub i: f64,
+}
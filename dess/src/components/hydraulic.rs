@@ -0,0 +1,265 @@
+use crate::imports::*;
+
+/// Tank component whose liquid volume above its reference level rises with
+/// pressure at a rate of `compliance`
+#[derive(HistoryMethods, BareClone, Default)]
+#[common_derives]
+#[pyo3_api(
+    #[new]
+    /// New tank with compliance `compliance` and initial pressure `p0`
+    pub fn __new__(compliance: f64, p0: f64) -> Self {
+        Self {
+            compliance,
+            state: TankState {
+                p: p0,
+                dp: Default::default(),
+            },
+            history: Default::default(),
+        }
+    }
+)]
+pub struct Tank {
+    /// change in volume per change in pressure \[m³/Pa\]
+    pub compliance: f64,
+    #[serde(default)]
+    pub state: TankState,
+    #[serde(default)]
+    pub history: TankStateHistoryVec,
+}
+
+impl Tank {
+    /// Returns volume \[m³\] stored above the volume at zero pressure
+    pub fn volume(&self) -> f64 {
+        self.compliance * self.state.p
+    }
+}
+
+impl HasState for Tank {
+    fn set_state(&mut self, val: f64) {
+        self.state.p = val;
+    }
+    fn state(&self) -> f64 {
+        self.state.p
+    }
+    fn deriv(&self) -> f64 {
+        self.state.dp
+    }
+    fn set_deriv(&mut self, val: f64) {
+        self.state.dp = val;
+    }
+    fn step_deriv(&mut self, val: f64) {
+        self.state.dp += val;
+    }
+    fn storage(&self) -> f64 {
+        self.compliance
+    }
+}
+
+/// Pressure reservoir component, e.g. an expansion tank or the atmosphere,
+/// whose pressure does not change with flow in or out
+#[derive(HistoryMethods, BareClone, Default)]
+#[common_derives]
+#[pyo3_api(
+    #[new]
+    /// New pressure reservoir with pressure `p0`
+    pub fn __new__(p0: f64) -> Self {
+        Self {
+            state: TankState {
+                p: p0,
+                dp: Default::default(),
+            },
+            history: Default::default(),
+        }
+    }
+)]
+pub struct PressureReservoir {
+    #[serde(default)]
+    pub state: TankState,
+    #[serde(default)]
+    pub history: TankStateHistoryVec,
+}
+
+impl HasState for PressureReservoir {
+    fn set_state(&mut self, val: f64) {
+        self.state.p = val;
+    }
+    fn state(&self) -> f64 {
+        self.state.p
+    }
+    fn deriv(&self) -> f64 {
+        self.state.dp
+    }
+    fn set_deriv(&mut self, _val: f64) {
+        self.state.dp = 0.0;
+    }
+    fn step_deriv(&mut self, _val: f64) {
+        self.state.dp = 0.0;
+    }
+    fn storage(&self) -> f64 {
+        f64::INFINITY
+    }
+}
+
+/// State for tracking pressure of [Tank] and [PressureReservoir]
+#[derive(Copy, HistoryVec, Default)]
+#[common_derives]
+#[pyo3_api]
+pub struct TankState {
+    /// pressure \[Pa\]
+    pub p: f64,
+    /// derivative of pressure w.r.t. time \[Pa/s\]
+    pub dp: f64,
+}
+
+/// Pipe component with pressure drop `r_laminar * q + k_turbulent * q * |q|`
+/// at volumetric flow rate `q`
+#[derive(HistoryMethods, BareClone, Default)]
+#[common_derives]
+#[pyo3_api(
+    #[new]
+    fn __new__(r_laminar: f64, k_turbulent: f64) -> Self {
+        Self {
+            r_laminar,
+            k_turbulent,
+            state: Default::default(),
+            history: Default::default(),
+        }
+    }
+)]
+pub struct Pipe {
+    /// laminar resistance \[Pa·s/m³\]
+    pub r_laminar: f64,
+    /// turbulent loss coefficient \[Pa·s²/m⁶\]
+    pub k_turbulent: f64,
+    #[serde(default)]
+    pub state: PipeState,
+    #[serde(default)]
+    pub history: PipeStateHistoryVec,
+}
+
+impl Pipe {
+    /// Returns pipe with Hagen-Poiseuille laminar resistance and Darcy
+    /// turbulent loss with friction factor `friction_factor`
+    ///
+    /// # Arguments
+    /// * `length` -- \[m\]
+    /// * `diameter` -- inner diameter \[m\]
+    /// * `density` -- fluid density \[kg/m³\]
+    /// * `viscosity` -- fluid dynamic viscosity \[Pa·s\]
+    /// * `friction_factor` -- Darcy friction factor \[-\]
+    pub fn from_geometry(
+        length: f64,
+        diameter: f64,
+        density: f64,
+        viscosity: f64,
+        friction_factor: f64,
+    ) -> Self {
+        let area = std::f64::consts::PI * diameter.powi(2) / 4.;
+        Self {
+            r_laminar: 128. * viscosity * length / (std::f64::consts::PI * diameter.powi(4)),
+            k_turbulent: friction_factor * length / diameter * density / (2. * area.powi(2)),
+            ..Default::default()
+        }
+    }
+
+    /// Returns pressure drop \[Pa\] at flow rate `q` \[m³/s\]
+    pub fn pressure_drop(&self, q: f64) -> f64 {
+        self.r_laminar * q + self.k_turbulent * q * q.abs()
+    }
+}
+
+impl Flow for Pipe {
+    fn flow(&self) -> f64 {
+        self.state.q
+    }
+    fn set_flow(&mut self, p0: &dyn HasState, p1: &dyn HasState) {
+        let dp = p0.state() - p1.state();
+        let (r, k) = (self.r_laminar, self.k_turbulent);
+        // root of `pressure_drop(q) = dp`, in a form that is also exact for `k = 0`
+        let denominator = r + (r * r + 4. * k * dp.abs()).sqrt();
+        // only zero for `dp = 0` with `r = 0`, or for a pipe without any losses
+        self.state.q = if denominator > 0. {
+            2. * dp / denominator
+        } else {
+            0.
+        };
+    }
+}
+
+/// Centrifugal pump component with quadratic pressure rise curve
+/// `speed² * shutoff_pressure - shutoff_pressure * (q / max_flow)²`, which
+/// drives flow from the first node it connects (inlet) to the second
+/// (outlet).  A pressure rise beyond shutoff gives no flow, as if a check
+/// valve were present, and so does a pump with non-positive
+/// `shutoff_pressure` or `max_flow`.
+#[derive(HistoryMethods, BareClone)]
+#[common_derives]
+#[pyo3_api(
+    #[new]
+    fn __new__(shutoff_pressure: f64, max_flow: f64) -> Self {
+        Self {
+            shutoff_pressure,
+            max_flow,
+            ..Default::default()
+        }
+    }
+)]
+pub struct Pump {
+    /// pressure rise \[Pa\] at zero flow and full speed
+    pub shutoff_pressure: f64,
+    /// flow rate \[m³/s\] at zero pressure rise and full speed
+    pub max_flow: f64,
+    /// fraction of full speed, scaling pressure with its square and flow
+    /// linearly
+    pub speed: f64,
+    #[serde(default)]
+    pub state: PipeState,
+    #[serde(default)]
+    pub history: PipeStateHistoryVec,
+}
+
+impl Default for Pump {
+    fn default() -> Self {
+        Self {
+            shutoff_pressure: Default::default(),
+            max_flow: Default::default(),
+            speed: 1.0,
+            state: Default::default(),
+            history: Default::default(),
+        }
+    }
+}
+
+impl Pump {
+    /// Returns pressure rise \[Pa\] at flow rate `q` \[m³/s\]
+    pub fn pressure_rise(&self, q: f64) -> f64 {
+        // shutoff pressure at zero flow even if `max_flow` is zero
+        let ratio = if q == 0. { 0. } else { q / self.max_flow };
+        self.shutoff_pressure * (self.speed.powi(2) - ratio.powi(2))
+    }
+}
+
+impl Flow for Pump {
+    fn flow(&self) -> f64 {
+        self.state.q
+    }
+    fn set_flow(&mut self, p0: &dyn HasState, p1: &dyn HasState) {
+        let rise = p1.state() - p0.state();
+        let shutoff = self.shutoff_pressure * self.speed.powi(2);
+        let degenerate = !(self.shutoff_pressure > 0. && self.max_flow > 0.);
+        self.state.q = if !degenerate && rise < shutoff {
+            self.max_flow * (self.speed.powi(2) - rise / self.shutoff_pressure).sqrt()
+        } else {
+            0.0
+        };
+    }
+}
+
+/// Struct for tracking flow variables in [Pipe] and [Pump]
+#[derive(Copy, HistoryVec, Default)]
+#[common_derives]
+#[pyo3_api]
+pub struct PipeState {
+    /// volumetric flow rate \[m³/s\]
+    pub q: f64,
+}
@@ -0,0 +1,340 @@
+//! Translational and rotational components.  Velocity (or angular velocity)
+//! is the potential and force (or torque) is the flow, with positive flow
+//! pushing the second connected node and holding back the first.
+
+use crate::imports::*;
+
+/// Mass component with mass, velocity state, and history
+#[derive(HistoryMethods, BareClone, Default)]
+#[common_derives]
+#[pyo3_api(
+    #[new]
+    /// New mass with mass `m` and initial velocity `v0`
+    pub fn __new__(m: f64, v0: f64) -> Self {
+        Self {
+            m,
+            state: MassState {
+                v: v0,
+                dv: Default::default(),
+            },
+            history: Default::default(),
+        }
+    }
+)]
+pub struct Mass {
+    /// mass \[kg\]
+    pub m: f64,
+    #[serde(default)]
+    pub state: MassState,
+    #[serde(default)]
+    pub history: MassStateHistoryVec,
+}
+
+impl HasState for Mass {
+    fn set_state(&mut self, val: f64) {
+        self.state.v = val;
+    }
+    fn state(&self) -> f64 {
+        self.state.v
+    }
+    fn deriv(&self) -> f64 {
+        self.state.dv
+    }
+    fn set_deriv(&mut self, val: f64) {
+        self.state.dv = val;
+    }
+    fn step_deriv(&mut self, val: f64) {
+        self.state.dv += val;
+    }
+    fn storage(&self) -> f64 {
+        self.m
+    }
+}
+
+/// Fixed ground, i.e. a node that never moves, for both translational and
+/// rotational components
+#[derive(HistoryMethods, BareClone, Default)]
+#[common_derives]
+#[pyo3_api(
+    #[new]
+    pub fn __new__() -> Self {
+        Self::default()
+    }
+)]
+pub struct FixedGround {
+    #[serde(default)]
+    pub state: MassState,
+    #[serde(default)]
+    pub history: MassStateHistoryVec,
+}
+
+impl HasState for FixedGround {
+    fn set_state(&mut self, _val: f64) {
+        self.state.v = 0.0;
+    }
+    fn state(&self) -> f64 {
+        0.0
+    }
+    fn deriv(&self) -> f64 {
+        0.0
+    }
+    fn set_deriv(&mut self, _val: f64) {
+        self.state.dv = 0.0;
+    }
+    fn step_deriv(&mut self, _val: f64) {
+        self.state.dv = 0.0;
+    }
+    fn storage(&self) -> f64 {
+        f64::INFINITY
+    }
+}
+
+/// State for tracking velocity of [Mass] and [FixedGround]
+#[derive(Copy, HistoryVec, Default)]
+#[common_derives]
+#[pyo3_api]
+pub struct MassState {
+    /// velocity \[m/s\]
+    pub v: f64,
+    /// acceleration \[m/s²\]
+    pub dv: f64,
+}
+
+/// Rotational inertia component with moment of inertia, angular velocity
+/// state, and history
+#[derive(HistoryMethods, BareClone, Default)]
+#[common_derives]
+#[pyo3_api(
+    #[new]
+    /// New inertia with moment of inertia `j` and initial angular velocity `omega0`
+    pub fn __new__(j: f64, omega0: f64) -> Self {
+        Self {
+            j,
+            state: InertiaState {
+                omega: omega0,
+                domega: Default::default(),
+            },
+            history: Default::default(),
+        }
+    }
+)]
+pub struct Inertia {
+    /// moment of inertia \[kg·m²\]
+    pub j: f64,
+    #[serde(default)]
+    pub state: InertiaState,
+    #[serde(default)]
+    pub history: InertiaStateHistoryVec,
+}
+
+impl HasState for Inertia {
+    fn set_state(&mut self, val: f64) {
+        self.state.omega = val;
+    }
+    fn state(&self) -> f64 {
+        self.state.omega
+    }
+    fn deriv(&self) -> f64 {
+        self.state.domega
+    }
+    fn set_deriv(&mut self, val: f64) {
+        self.state.domega = val;
+    }
+    fn step_deriv(&mut self, val: f64) {
+        self.state.domega += val;
+    }
+    fn storage(&self) -> f64 {
+        self.j
+    }
+}
+
+/// State for tracking angular velocity of [Inertia]
+#[derive(Copy, HistoryVec, Default)]
+#[common_derives]
+#[pyo3_api]
+pub struct InertiaState {
+    /// angular velocity \[rad/s\]
+    pub omega: f64,
+    /// angular acceleration \[rad/s²\]
+    pub domega: f64,
+}
+
+/// Linear spring component, whose force is a state that changes at a rate
+/// of its stiffness times the difference in velocity across it.  Fields of
+/// this type are marked `#[use_flow_state]`.
+#[derive(HistoryMethods, BareClone, Default)]
+#[common_derives]
+#[pyo3_api(
+    #[new]
+    /// New spring with stiffness `k` and initial compressive force `f0`
+    pub fn __new__(k: f64, f0: f64) -> Self {
+        Self {
+            k,
+            state: SpringState {
+                f: f0,
+                df: Default::default(),
+            },
+            history: Default::default(),
+        }
+    }
+)]
+pub struct Spring {
+    /// stiffness \[N/m\]
+    pub k: f64,
+    #[serde(default)]
+    pub state: SpringState,
+    #[serde(default)]
+    pub history: SpringStateHistoryVec,
+}
+
+impl Spring {
+    /// Returns compression \[m\] from free length
+    pub fn deflection(&self) -> f64 {
+        self.state.f / self.k
+    }
+}
+
+impl Flow for Spring {
+    fn flow(&self) -> f64 {
+        self.state.f
+    }
+    fn set_flow(&mut self, p0: &dyn HasState, p1: &dyn HasState) {
+        self.state.df = self.k * (p0.state() - p1.state());
+    }
+}
+
+impl HasFlowState for Spring {
+    fn set_flow_state(&mut self, val: f64) {
+        self.state.f = val;
+    }
+    fn flow_deriv(&self) -> f64 {
+        self.state.df
+    }
+    fn set_flow_deriv(&mut self, val: f64) {
+        self.state.df = val;
+    }
+    fn inertance(&self) -> f64 {
+        1. / self.k
+    }
+}
+
+/// State for tracking force of [Spring]
+#[derive(Copy, HistoryVec, Default)]
+#[common_derives]
+#[pyo3_api]
+pub struct SpringState {
+    /// compressive force \[N\]
+    pub f: f64,
+    /// derivative of force w.r.t. time \[N/s\]
+    pub df: f64,
+}
+
+/// Torsion spring component, whose torque is a state that changes at a
+/// rate of its stiffness times the difference in angular velocity across
+/// it.  Fields of this type are marked `#[use_flow_state]`.
+#[derive(HistoryMethods, BareClone, Default)]
+#[common_derives]
+#[pyo3_api(
+    #[new]
+    /// New torsion spring with stiffness `k` and initial torque `tau0`
+    pub fn __new__(k: f64, tau0: f64) -> Self {
+        Self {
+            k,
+            state: TorsionSpringState {
+                tau: tau0,
+                dtau: Default::default(),
+            },
+            history: Default::default(),
+        }
+    }
+)]
+pub struct TorsionSpring {
+    /// torsional stiffness \[N·m/rad\]
+    pub k: f64,
+    #[serde(default)]
+    pub state: TorsionSpringState,
+    #[serde(default)]
+    pub history: TorsionSpringStateHistoryVec,
+}
+
+impl TorsionSpring {
+    /// Returns twist \[rad\] from unloaded angle
+    pub fn twist(&self) -> f64 {
+        self.state.tau / self.k
+    }
+}
+
+impl Flow for TorsionSpring {
+    fn flow(&self) -> f64 {
+        self.state.tau
+    }
+    fn set_flow(&mut self, p0: &dyn HasState, p1: &dyn HasState) {
+        self.state.dtau = self.k * (p0.state() - p1.state());
+    }
+}
+
+impl HasFlowState for TorsionSpring {
+    fn set_flow_state(&mut self, val: f64) {
+        self.state.tau = val;
+    }
+    fn flow_deriv(&self) -> f64 {
+        self.state.dtau
+    }
+    fn set_flow_deriv(&mut self, val: f64) {
+        self.state.dtau = val;
+    }
+    fn inertance(&self) -> f64 {
+        1. / self.k
+    }
+}
+
+/// State for tracking torque of [TorsionSpring]
+#[derive(Copy, HistoryVec, Default)]
+#[common_derives]
+#[pyo3_api]
+pub struct TorsionSpringState {
+    /// torque \[N·m\]
+    pub tau: f64,
+    /// derivative of torque w.r.t. time \[N·m/s\]
+    pub dtau: f64,
+}
+
+/// Viscous damper component for translation or rotation
+#[derive(HistoryMethods, BareClone, Default)]
+#[common_derives]
+#[pyo3_api(
+    #[new]
+    fn __new__(b: f64) -> Self {
+        Self {
+            b,
+            state: Default::default(),
+            history: Default::default(),
+        }
+    }
+)]
+pub struct Damper {
+    /// damping coefficient \[N·s/m\] or \[N·m·s/rad\]
+    pub b: f64,
+    #[serde(default)]
+    pub state: DamperState,
+    #[serde(default)]
+    pub history: DamperStateHistoryVec,
+}
+
+impl Flow for Damper {
+    fn flow(&self) -> f64 {
+        self.state.f
+    }
+    fn set_flow(&mut self, p0: &dyn HasState, p1: &dyn HasState) {
+        self.state.f = self.b * (p0.state() - p1.state());
+    }
+}
+
+/// Struct for tracking flow variables in [Damper]
+#[derive(Copy, HistoryVec, Default)]
+#[common_derives]
+#[pyo3_api]
+pub struct DamperState {
+    /// force \[N\] or torque \[N·m\]
+    pub f: f64,
+}
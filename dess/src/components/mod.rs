@@ -0,0 +1,18 @@
+//! Reusable components, grouped by physical domain.  Components that store
+//! a potential (e.g. temperature, voltage, pressure, or velocity) implement
+//! [HasState](crate::traits_and_macros::HasState), and components that carry
+//! a flow between two of them implement [Flow](crate::traits_and_macros::Flow).
+//! Components that inject a flow into one of them implement
+//! [Source](crate::traits_and_macros::Source).
+
+pub mod electrical;
+pub mod hydraulic;
+pub mod mechanical;
+pub mod source;
+pub mod thermal;
+
+pub use electrical::*;
+pub use hydraulic::*;
+pub use mechanical::*;
+pub use source::*;
+pub use thermal::*;
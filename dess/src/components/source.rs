@@ -0,0 +1,140 @@
+//! Sources that inject a prescribed flow into a single node in any domain,
+//! e.g. a heat rate into a thermal mass or a current into a capacitor.
+//! They are connected with a pair, e.g. `(m1, q1)`, in
+//! [connect_states](crate::connect_states) and
+//! [update_derivs](crate::update_derivs).
+
+use crate::imports::*;
+use crate::signal::*;
+use crate::time_series::HasBreakpoints;
+use std::sync::Arc;
+
+/// Flow \[e.g. W\] from time \[s\] and the potential of the connected node
+/// \[e.g. °C\]
+pub type SourceFnType = dyn Fn(f64, f64) -> f64 + Send + Sync;
+
+/// Closure computing the flow of a [FlowSource].  Clones share the same
+/// closure and are equal, while distinct closures are unordered.  Closures
+/// serialize as a marker that fails to deserialize, so that e.g. `reset`,
+/// `resume`, or `with_patch` of a system holding one fails instead of
+/// silently dropping it.
+#[derive(Clone)]
+pub struct SourceFn(pub Arc<SourceFnType>);
+
+/// Serialized form of a [SourceFn]
+const SOURCE_FN_MARKER: &str = "<closure>";
+
+impl Serialize for SourceFn {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(SOURCE_FN_MARKER)
+    }
+}
+
+impl<'de> Deserialize<'de> for SourceFn {
+    fn deserialize<D: serde::Deserializer<'de>>(_deserializer: D) -> Result<Self, D::Error> {
+        Err(serde::de::Error::custom(
+            "FlowSource closure cannot be deserialized and must be reattached with `FlowSource::function`",
+        ))
+    }
+}
+
+impl std::fmt::Debug for SourceFn {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "SourceFn")
+    }
+}
+
+impl PartialEq for SourceFn {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl PartialOrd for SourceFn {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        (self == other).then_some(std::cmp::Ordering::Equal)
+    }
+}
+
+/// Source component that injects flow into the node it is attached to, with
+/// positive flow going into the node.  Flow follows `signal`, e.g. in YAML
+/// `signal: !Step {time: 1.0, after: 5.0}`, unless computed by closure
+/// `func`, which cannot be deserialized (see [SourceFn]).  Fields of this type may also be
+/// marked `#[breakpoints]` so that the solver lands on jumps in the signal.
+#[derive(HistoryMethods, BareClone, Default)]
+#[common_derives]
+#[pyo3_api(
+    #[new]
+    /// New source with constant flow `flow`
+    fn __new__(flow: f64) -> Self {
+        Self::constant(flow)
+    }
+)]
+pub struct FlowSource {
+    #[skip_get]
+    pub signal: SignalTypes,
+    #[serde(default)]
+    #[skip_get]
+    pub func: Option<SourceFn>,
+    #[serde(default)]
+    pub state: SourceState,
+    #[serde(default)]
+    pub history: SourceStateHistoryVec,
+}
+
+impl FlowSource {
+    /// Returns source with constant flow `flow`
+    pub fn constant(flow: f64) -> Self {
+        Self {
+            signal: SignalTypes::Constant(Constant(flow)),
+            ..Default::default()
+        }
+    }
+
+    /// Returns source whose flow follows `signal`
+    pub fn signal(signal: SignalTypes) -> Self {
+        Self {
+            signal,
+            ..Default::default()
+        }
+    }
+
+    /// Returns source whose flow is computed by `f(time, potential)`, e.g. a
+    /// thermostat that heats only below a set point
+    pub fn function(f: impl Fn(f64, f64) -> f64 + Send + Sync + 'static) -> Self {
+        Self {
+            func: Some(SourceFn(Arc::new(f))),
+            ..Default::default()
+        }
+    }
+}
+
+impl Source for FlowSource {
+    fn flow(&self) -> f64 {
+        self.state.flow
+    }
+    fn set_source_flow(&mut self, p: &dyn HasState, t: f64) {
+        self.state.flow = match &self.func {
+            Some(f) => (f.0)(t, p.state()),
+            None => self.signal.value(t),
+        };
+    }
+}
+
+impl HasBreakpoints for FlowSource {
+    fn next_breakpoint(&self, t: f64) -> Option<f64> {
+        match self.func {
+            Some(_) => None,
+            None => self.signal.next_breakpoint(t),
+        }
+    }
+}
+
+/// Struct for tracking flow variable in [FlowSource]
+#[derive(Copy, HistoryVec, Default)]
+#[common_derives]
+#[pyo3_api]
+pub struct SourceState {
+    /// flow into the node, e.g. heat rate \[W\] or current \[A\]
+    pub flow: f64,
+}
@@ -0,0 +1,557 @@
+use crate::imports::*;
+use crate::time_series::{interpolate, Interpolation};
+
+/// Heat source, e.g. a heater or electrical losses, injecting heat rate
+/// \[W\] into a thermal mass
+pub type HeatSource = super::FlowSource;
+
+/// ThermalMass component with capacitance, state, and history
+#[derive(HistoryMethods, BareClone, Default)]
+#[common_derives]
+#[pyo3_api(
+    #[new]
+    /// New thermal mass with capacitance `c` and initial temperature `t0`
+    pub fn __new__(c: f64, temp0: f64) -> Self {
+        Self {
+            c,
+            state: ThermalMassState {
+                temp: temp0,
+                dtemp: Default::default(),
+            },
+            history: Default::default(),
+        }
+    }
+)]
+pub struct ThermalMass {
+    /// thermal capacitance \[J/K\]
+    pub c: f64,
+    #[serde(default)]
+    pub state: ThermalMassState,
+    #[serde(default)]
+    pub history: ThermalMassStateHistoryVec,
+}
+
+impl HasState for ThermalMass {
+    fn set_state(&mut self, val: f64) {
+        self.state.temp = val;
+    }
+    fn state(&self) -> f64 {
+        self.state.temp
+    }
+    fn deriv(&self) -> f64 {
+        self.state.dtemp
+    }
+    fn set_deriv(&mut self, val: f64) {
+        self.state.dtemp = val;
+    }
+    fn step_deriv(&mut self, val: f64) {
+        self.state.dtemp += val;
+    }
+    fn storage(&self) -> f64 {
+        self.c
+    }
+}
+
+/// ThermalReservoir component with capacitance, state, and history
+#[derive(HistoryMethods, BareClone, Default)]
+#[common_derives]
+#[pyo3_api(
+    #[new]
+    /// New thermal reservoir with initial temperature `t0`
+    pub fn __new__(temp0: f64) -> Self {
+        Self {
+            state: ThermalMassState {
+                temp: temp0,
+                dtemp: Default::default(),
+            },
+            history: Default::default(),
+        }
+    }
+)]
+pub struct ThermalReservoir {
+    #[serde(default)]
+    pub state: ThermalMassState,
+    #[serde(default)]
+    pub history: ThermalMassStateHistoryVec,
+}
+
+impl HasState for ThermalReservoir {
+    fn set_state(&mut self, val: f64) {
+        self.state.temp = val;
+    }
+    fn state(&self) -> f64 {
+        self.state.temp
+    }
+    fn deriv(&self) -> f64 {
+        self.state.dtemp
+    }
+    fn set_deriv(&mut self, _val: f64) {
+        self.state.dtemp = 0.0;
+    }
+    fn step_deriv(&mut self, val: f64) {
+        self.state.dtemp += val;
+    }
+    fn storage(&self) -> f64 {
+        f64::INFINITY
+    }
+}
+
+/// State for tracking temperature of [ThermalMass]
+#[derive(Copy, HistoryVec, Default)]
+#[common_derives]
+#[pyo3_api]
+pub struct ThermalMassState {
+    /// temperature \[°C\]
+    pub temp: f64,
+    /// derivative of temperature w.r.t. time \[°C/s\]
+    pub dtemp: f64,
+}
+
+/// Conductance component
+#[derive(HistoryMethods, BareClone)]
+#[pyo3_api(
+    #[new]
+    fn __new__(h: f64) -> Self {
+        Self {
+            h,
+            state: ConductanceState {
+                q: Default::default(),
+            },
+            history: ConductanceStateHistoryVec {
+                q: Default::default(),
+            },
+        }
+    }
+)]
+#[common_derives]
+#[derive(Default)]
+pub struct Conductance {
+    /// Thermal conductance \[W/K\] between two temperatures
+    pub h: f64,
+    #[serde(default)]
+    pub state: ConductanceState,
+    #[serde(default)]
+    pub history: ConductanceStateHistoryVec,
+}
+
+impl Flow for Conductance {
+    fn flow(&self) -> f64 {
+        self.state.q
+    }
+    fn set_flow(&mut self, p0: &dyn HasState, p1: &dyn HasState) {
+        self.state.q = self.h * (p0.state() - p1.state());
+    }
+}
+
+/// Struct for tracking flow variables in Conductance
+#[derive(Copy, HistoryVec, Default)]
+#[common_derives]
+#[pyo3_api]
+pub struct ConductanceState {
+    /// Heat transfer rate \[W\]
+    pub q: f64,
+}
+
+/// Units of temperature states, for components whose physics depends on
+/// absolute temperature
+#[derive(Default, Copy)]
+#[common_derives]
+pub enum TemperatureUnits {
+    /// degrees Celsius, as used by [ThermalMassState]
+    #[default]
+    Celsius,
+    Kelvin,
+}
+
+impl TemperatureUnits {
+    /// Returns temperature `temp` in these units converted to Kelvin
+    pub fn to_kelvin(&self, temp: f64) -> f64 {
+        match self {
+            TemperatureUnits::Celsius => temp + 273.15,
+            TemperatureUnits::Kelvin => temp,
+        }
+    }
+}
+
+/// Stefan-Boltzmann constant \[W/(m²·K⁴)\]
+pub const STEFAN_BOLTZMANN: f64 = 5.670_374_419e-8;
+
+/// Radiative heat transfer between two surfaces, `ε σ A (T0⁴ - T1⁴)` with
+/// temperatures converted to Kelvin
+#[derive(HistoryMethods, BareClone, Default)]
+#[common_derives]
+#[pyo3_api(
+    #[new]
+    fn __new__(emissivity: f64, area: f64) -> Self {
+        Self {
+            emissivity,
+            area,
+            ..Default::default()
+        }
+    }
+)]
+pub struct RadiativeConductance {
+    /// effective emissivity \[-\], including any view factor
+    pub emissivity: f64,
+    /// radiating area \[m²\]
+    pub area: f64,
+    /// units of the connected temperatures
+    #[serde(default)]
+    #[skip_get]
+    pub units: TemperatureUnits,
+    #[serde(default)]
+    pub state: ConductanceState,
+    #[serde(default)]
+    pub history: ConductanceStateHistoryVec,
+}
+
+impl Flow for RadiativeConductance {
+    fn flow(&self) -> f64 {
+        self.state.q
+    }
+    fn set_flow(&mut self, p0: &dyn HasState, p1: &dyn HasState) {
+        let t0 = self.units.to_kelvin(p0.state());
+        let t1 = self.units.to_kelvin(p1.state());
+        self.state.q = self.emissivity * STEFAN_BOLTZMANN * self.area * (t0.powi(4) - t1.powi(4));
+    }
+}
+
+/// Correlation giving the Nusselt number of a [ConvectiveConductance]
+#[common_derives]
+pub enum NusseltCorrelation {
+    /// fixed Nusselt number
+    Constant { nu: f64 },
+    /// turbulent forced convection inside a pipe, `0.023 Re^0.8 Pr^0.4`
+    DittusBoelter { re: f64, pr: f64 },
+    /// laminar forced convection along a flat plate, `0.664 Re^0.5 Pr^(1/3)`
+    FlatPlateLaminar { re: f64, pr: f64 },
+    /// natural convection along a vertical plate (Churchill-Chu), with
+    /// Rayleigh number computed from the temperature difference
+    ///
+    /// # Fields
+    /// * `pr` -- Prandtl number \[-\]
+    /// * `beta` -- thermal expansion coefficient \[1/K\]
+    /// * `nu_kin` -- kinematic viscosity \[m²/s\]
+    /// * `alpha` -- thermal diffusivity \[m²/s\]
+    ChurchillChu {
+        pr: f64,
+        beta: f64,
+        nu_kin: f64,
+        alpha: f64,
+    },
+}
+
+impl Default for NusseltCorrelation {
+    fn default() -> Self {
+        Self::Constant { nu: 1.0 }
+    }
+}
+
+impl NusseltCorrelation {
+    /// Returns Nusselt number for temperature difference `dtemp` across
+    /// characteristic length `length` \[m\]
+    pub fn nusselt(&self, dtemp: f64, length: f64) -> f64 {
+        match *self {
+            NusseltCorrelation::Constant { nu } => nu,
+            NusseltCorrelation::DittusBoelter { re, pr } => 0.023 * re.powf(0.8) * pr.powf(0.4),
+            NusseltCorrelation::FlatPlateLaminar { re, pr } => {
+                0.664 * re.powf(0.5) * pr.powf(1. / 3.)
+            }
+            NusseltCorrelation::ChurchillChu {
+                pr,
+                beta,
+                nu_kin,
+                alpha,
+            } => {
+                let ra = 9.81 * beta * dtemp.abs() * length.powi(3) / (nu_kin * alpha);
+                (0.825
+                    + 0.387 * ra.powf(1. / 6.) / (1. + (0.492 / pr).powf(9. / 16.)).powf(8. / 27.))
+                .powi(2)
+            }
+        }
+    }
+}
+
+/// Convective heat transfer between a surface and a fluid, `h A (T0 - T1)`
+/// with `h = Nu k / L` from a [NusseltCorrelation]
+#[derive(HistoryMethods, BareClone, Default)]
+#[common_derives]
+#[pyo3_api(
+    #[new]
+    /// New conductance with constant Nusselt number `nu`
+    fn __new__(area: f64, length: f64, k_fluid: f64, nu: f64) -> Self {
+        Self {
+            area,
+            length,
+            k_fluid,
+            correlation: NusseltCorrelation::Constant { nu },
+            ..Default::default()
+        }
+    }
+)]
+pub struct ConvectiveConductance {
+    /// wetted area \[m²\]
+    pub area: f64,
+    /// characteristic length \[m\], e.g. pipe diameter or plate height
+    pub length: f64,
+    /// thermal conductivity of the fluid \[W/(m·K)\]
+    pub k_fluid: f64,
+    #[skip_get]
+    pub correlation: NusseltCorrelation,
+    #[serde(default)]
+    pub state: ConductanceState,
+    #[serde(default)]
+    pub history: ConductanceStateHistoryVec,
+}
+
+impl ConvectiveConductance {
+    /// Returns heat transfer coefficient \[W/(m²·K)\] for temperature
+    /// difference `dtemp`
+    pub fn h(&self, dtemp: f64) -> f64 {
+        self.correlation.nusselt(dtemp, self.length) * self.k_fluid / self.length
+    }
+}
+
+impl Flow for ConvectiveConductance {
+    fn flow(&self) -> f64 {
+        self.state.q
+    }
+    fn set_flow(&mut self, p0: &dyn HasState, p1: &dyn HasState) {
+        let dtemp = p0.state() - p1.state();
+        self.state.q = self.h(dtemp) * self.area * dtemp;
+    }
+}
+
+/// Thermal mass whose capacitance depends on temperature, interpolated
+/// linearly in a table and held constant outside of it.  The table is
+/// checked with [Self::validate] when deserialized.
+#[derive(HistoryMethods, BareClone)]
+#[common_derives]
+#[serde(try_from = "VariableThermalMassUnchecked")]
+#[pyo3_api(
+    #[new]
+    /// New thermal mass with capacitance `c` at each of `temps` and initial
+    /// temperature `temp0`
+    pub fn __new__(temps: Vec<f64>, c: Vec<f64>, temp0: f64) -> PyResult<Self> {
+        Ok(Self::new(temps, c, temp0)?)
+    }
+)]
+pub struct VariableThermalMass {
+    /// strictly increasing temperatures \[°C\] of `c` table
+    pub temps: Vec<f64>,
+    /// thermal capacitance \[J/K\] at each of `temps`
+    pub c: Vec<f64>,
+    #[serde(default)]
+    pub state: ThermalMassState,
+    #[serde(default)]
+    pub history: ThermalMassStateHistoryVec,
+}
+
+/// Constant capacitance of 1 J/K
+impl Default for VariableThermalMass {
+    fn default() -> Self {
+        Self {
+            temps: vec![0.0],
+            c: vec![1.0],
+            state: Default::default(),
+            history: Default::default(),
+        }
+    }
+}
+
+impl VariableThermalMass {
+    /// Returns new thermal mass with capacitance `c` at each of `temps` and
+    /// initial temperature `temp0`, failing if the table is not valid
+    pub fn new(temps: Vec<f64>, c: Vec<f64>, temp0: f64) -> anyhow::Result<Self> {
+        let mass = Self {
+            temps,
+            c,
+            state: ThermalMassState {
+                temp: temp0,
+                dtemp: Default::default(),
+            },
+            history: Default::default(),
+        };
+        mass.validate()?;
+        Ok(mass)
+    }
+
+    /// Checks that the table has at least one point, `temps` and `c` have
+    /// the same length, and `temps` is strictly increasing
+    pub fn validate(&self) -> anyhow::Result<()> {
+        if self.temps.is_empty() {
+            return Err(anyhow!("Capacitance table has no points"));
+        }
+        if self.temps.len() != self.c.len() {
+            return Err(anyhow!(
+                "Capacitance table has {} temperatures but {} capacitances",
+                self.temps.len(),
+                self.c.len()
+            ));
+        }
+        if let Some(k) = self.temps.windows(2).position(|w| w[1] <= w[0]) {
+            return Err(anyhow!(
+                "Capacitance table temperatures must be strictly increasing, but temps[{}] = {} is followed by {}",
+                k,
+                self.temps[k],
+                self.temps[k + 1]
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// [VariableThermalMass] as deserialized, before its table is validated
+#[derive(Deserialize)]
+struct VariableThermalMassUnchecked {
+    temps: Vec<f64>,
+    c: Vec<f64>,
+    #[serde(default)]
+    state: ThermalMassState,
+    #[serde(default)]
+    history: ThermalMassStateHistoryVec,
+}
+
+impl TryFrom<VariableThermalMassUnchecked> for VariableThermalMass {
+    type Error = anyhow::Error;
+
+    fn try_from(mass: VariableThermalMassUnchecked) -> anyhow::Result<Self> {
+        let mass = Self {
+            temps: mass.temps,
+            c: mass.c,
+            state: mass.state,
+            history: mass.history,
+        };
+        mass.validate()?;
+        Ok(mass)
+    }
+}
+
+impl HasState for VariableThermalMass {
+    fn set_state(&mut self, val: f64) {
+        self.state.temp = val;
+    }
+    fn state(&self) -> f64 {
+        self.state.temp
+    }
+    fn deriv(&self) -> f64 {
+        self.state.dtemp
+    }
+    fn set_deriv(&mut self, val: f64) {
+        self.state.dtemp = val;
+    }
+    fn step_deriv(&mut self, val: f64) {
+        self.state.dtemp += val;
+    }
+    fn storage(&self) -> f64 {
+        interpolate(&self.temps, &self.c, Interpolation::Linear, self.state.temp)
+    }
+}
+
+/// Wall discretized through its thickness into layers of equal capacitance,
+/// with one temperature state per layer.  Fields of this type are marked
+/// `#[use_state]` like any other thermal mass, and its faces exchange heat
+/// with neighboring nodes through [Self::connect_faces].
+#[derive(HistoryMethods, BareClone, Default)]
+#[common_derives]
+#[pyo3_api(
+    #[new]
+    /// New wall with `n_layers` layers at initial temperature `temp0`
+    pub fn __new__(n_layers: usize, c: f64, h: f64, temp0: f64) -> PyResult<Self> {
+        Ok(Self::new(n_layers, c, h, temp0)?)
+    }
+)]
+pub struct LayeredWall {
+    /// thermal capacitance \[J/K\] of each layer
+    pub c: f64,
+    /// thermal conductance \[W/K\] between adjacent layers and between each
+    /// face layer and the node it faces
+    pub h: f64,
+    pub state: LayeredWallState,
+    #[serde(default)]
+    pub history: LayeredWallStateHistoryVec,
+}
+
+impl LayeredWall {
+    /// Returns wall with `n_layers` layers at initial temperature `temp0`,
+    /// failing if `n_layers` is zero
+    pub fn new(n_layers: usize, c: f64, h: f64, temp0: f64) -> anyhow::Result<Self> {
+        if n_layers == 0 {
+            return Err(anyhow!("Layered wall must have at least one layer"));
+        }
+        Ok(Self {
+            c,
+            h,
+            state: LayeredWallState {
+                temps: vec![temp0; n_layers],
+                dtemps: vec![0.0; n_layers],
+                ..Default::default()
+            },
+            history: Default::default(),
+        })
+    }
+
+    /// Sets heat flow between layers and between node `p0` and the first
+    /// layer and the last layer and node `p1`, and steps time derivatives
+    /// of all of them accordingly.  A wall without layers, e.g.
+    /// `LayeredWall::default()`, carries no heat.
+    pub fn connect_faces(&mut self, p0: &mut dyn HasState, p1: &mut dyn HasState) {
+        let (c, h) = (self.c, self.h);
+        let temps = &self.state.temps;
+        let dtemps = &mut self.state.dtemps;
+        let n = temps.len();
+        if n == 0 {
+            self.state.q0 = 0.0;
+            self.state.q1 = 0.0;
+            return;
+        }
+        for i in 0..n - 1 {
+            let q = h * (temps[i] - temps[i + 1]);
+            dtemps[i] -= q / c;
+            dtemps[i + 1] += q / c;
+        }
+        self.state.q0 = h * (p0.state() - temps[0]);
+        self.state.q1 = h * (temps[n - 1] - p1.state());
+        dtemps[0] += self.state.q0 / c;
+        dtemps[n - 1] -= self.state.q1 / c;
+        p0.step_deriv(-self.state.q0 / p0.storage());
+        p1.step_deriv(self.state.q1 / p1.storage());
+    }
+}
+
+impl HasStateSlice for LayeredWall {
+    fn states(&self) -> Vec<f64> {
+        self.state.temps.clone()
+    }
+    fn set_states(&mut self, val: &[f64]) {
+        self.state.temps = val.to_vec();
+    }
+    fn derivs(&self) -> Vec<f64> {
+        self.state.dtemps.clone()
+    }
+    fn set_derivs(&mut self, val: &[f64]) {
+        self.state.dtemps = val.to_vec();
+    }
+    fn storages(&self) -> Vec<f64> {
+        vec![self.c; self.state.temps.len()]
+    }
+}
+
+/// State for tracking layer temperatures of [LayeredWall]
+#[derive(HistoryVec, Default)]
+#[common_derives]
+#[pyo3_api]
+pub struct LayeredWallState {
+    /// temperature \[°C\] of each layer, from the face of `p0` to that of `p1`
+    /// in [LayeredWall::connect_faces]
+    pub temps: Vec<f64>,
+    /// derivative of temperature w.r.t. time \[°C/s\] of each layer
+    #[serde(default)]
+    pub dtemps: Vec<f64>,
+    /// heat flow \[W\] into the first layer
+    #[serde(default)]
+    pub q0: f64,
+    /// heat flow \[W\] out of the last layer
+    #[serde(default)]
+    pub q1: f64,
+}
@@ -0,0 +1,230 @@
+use crate::imports::*;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+
+/// Parameter varied by a [Sweep] between `min` and `max`, addressed by
+/// `path` like `m2.c` or JSON pointer `/m2/c` into the serialized system
+#[common_derives]
+pub struct SweepParameter {
+    pub path: String,
+    pub min: f64,
+    pub max: f64,
+}
+
+/// How a [Sweep] samples its parameters
+#[common_derives]
+pub enum Sampling {
+    /// every combination of `levels` evenly spaced values per parameter,
+    /// including `min` and `max`, with the first parameter varying slowest
+    FullFactorial { levels: usize },
+    /// `n` samples that cover each of `n` equal intervals of every
+    /// parameter exactly once
+    LatinHypercube { n: usize, seed: u64 },
+    /// first `n` points of an Owen-scrambled Sobol sequence
+    Sobol { n: usize, seed: u32 },
+}
+
+/// Scalar reduction of a history column, e.g. `m3.temp` or `h12.q`
+#[common_derives]
+pub enum Metric {
+    /// last value
+    Final { column: String },
+    /// maximum value
+    Max { column: String },
+    /// minimum value
+    Min { column: String },
+    /// arithmetic mean
+    Mean { column: String },
+}
+
+impl Metric {
+    /// Returns name of metric, e.g. `max(h12.q)`
+    pub fn name(&self) -> String {
+        match self {
+            Metric::Final { column } => format!("final({})", column),
+            Metric::Max { column } => format!("max({})", column),
+            Metric::Min { column } => format!("min({})", column),
+            Metric::Mean { column } => format!("mean({})", column),
+        }
+    }
+
+    /// Evaluates metric on `columns`
+    pub fn evaluate(&self, columns: &Columns) -> anyhow::Result<f64> {
+        let (Metric::Final { column }
+        | Metric::Max { column }
+        | Metric::Min { column }
+        | Metric::Mean { column }) = self;
+        let values = columns
+            .get(column)
+            .ok_or_else(|| anyhow!("History has no column `{}`", column))?;
+        if values.is_empty() {
+            return Err(anyhow!("History column `{}` is empty", column));
+        }
+        Ok(match self {
+            Metric::Final { .. } => *values.last().unwrap(),
+            Metric::Max { .. } => values.iter().cloned().fold(f64::NEG_INFINITY, f64::max),
+            Metric::Min { .. } => values.iter().cloned().fold(f64::INFINITY, f64::min),
+            Metric::Mean { .. } => values.iter().sum::<f64>() / values.len() as f64,
+        })
+    }
+}
+
+/// Design of experiments over named parameters, evaluated as a table of
+/// scalar metrics.  Can be read from YAML, e.g.
+/// ```yaml
+/// parameters:
+///   - {path: m2.c, min: 1.0, max: 3.0}
+///   - {path: h12.h, min: 2.0, max: 8.0}
+/// sampling: !LatinHypercube {n: 20, seed: 42}
+/// metrics:
+///   - !Final {column: m3.temp}
+///   - !Max {column: h12.q}
+/// ```
+#[common_derives]
+pub struct Sweep {
+    pub parameters: Vec<SweepParameter>,
+    pub sampling: Sampling,
+    pub metrics: Vec<Metric>,
+}
+
+impl Sweep {
+    /// Returns sampled parameter values, one `Vec` per run in the order of
+    /// [Self::parameters]
+    pub fn samples(&self) -> Vec<Vec<f64>> {
+        let n_params = self.parameters.len();
+        // points in the unit hypercube
+        let unit: Vec<Vec<f64>> = match &self.sampling {
+            Sampling::FullFactorial { levels } => {
+                let levels = (*levels).max(1);
+                let n = levels.pow(n_params as u32);
+                let level = |k: usize| {
+                    if levels == 1 {
+                        0.5
+                    } else {
+                        k as f64 / (levels - 1) as f64
+                    }
+                };
+                (0..n)
+                    .map(|i| {
+                        (0..n_params)
+                            .map(|j| level(i / levels.pow((n_params - 1 - j) as u32) % levels))
+                            .collect()
+                    })
+                    .collect()
+            }
+            Sampling::LatinHypercube { n, seed } => {
+                let mut rng = StdRng::seed_from_u64(*seed);
+                let mut points = vec![vec![0.0; n_params]; *n];
+                for j in 0..n_params {
+                    let mut strata: Vec<usize> = (0..*n).collect();
+                    strata.shuffle(&mut rng);
+                    for (point, stratum) in points.iter_mut().zip(strata) {
+                        point[j] = (stratum as f64 + rng.gen::<f64>()) / *n as f64;
+                    }
+                }
+                points
+            }
+            Sampling::Sobol { n, seed } => (0..*n as u32)
+                .map(|i| {
+                    (0..n_params as u32)
+                        .map(|j| sobol_burley::sample(i, j, *seed) as f64)
+                        .collect()
+                })
+                .collect(),
+        };
+        unit.into_iter()
+            .map(|point| {
+                point
+                    .iter()
+                    .zip(&self.parameters)
+                    .map(|(u, p)| p.min + u * (p.max - p.min))
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Returns one copy of `base` per sample with parameters set
+    pub fn systems<S: SerdeAPI>(&self, base: &S) -> anyhow::Result<Vec<S>> {
+        let paths: Vec<&str> = self.parameters.iter().map(|p| p.path.as_str()).collect();
+        with_path_values(base, &paths, &self.samples())
+    }
+
+    /// Runs all samples of `base` -- in parallel with the `rayon` feature --
+    /// and returns table with one row per sample, with a column for every
+    /// parameter path followed by a column for every metric
+    pub fn run<S: Simulate + SerdeAPI>(&self, base: &S) -> anyhow::Result<Columns> {
+        let systems = self.systems(base)?;
+        let samples = self.samples();
+        let histories: Vec<Columns> = walk_all(systems)?
+            .iter()
+            .map(|sys| sys.history_columns())
+            .collect();
+        let mut table = Columns::new();
+        for (j, p) in self.parameters.iter().enumerate() {
+            table.push(p.path.clone(), samples.iter().map(|s| s[j]).collect());
+        }
+        for metric in &self.metrics {
+            table.push(
+                metric.name(),
+                histories
+                    .iter()
+                    .map(|history| metric.evaluate(history))
+                    .collect::<anyhow::Result<Vec<f64>>>()?,
+            );
+        }
+        Ok(table)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_samples() {
+        let mut sweep = Sweep {
+            parameters: vec![
+                SweepParameter {
+                    path: "m2.c".into(),
+                    min: 1.0,
+                    max: 3.0,
+                },
+                SweepParameter {
+                    path: "h12.h".into(),
+                    min: 2.0,
+                    max: 8.0,
+                },
+            ],
+            sampling: Sampling::FullFactorial { levels: 3 },
+            metrics: vec![],
+        };
+        let samples = sweep.samples();
+        assert_eq!(samples.len(), 9);
+        assert_eq!(
+            samples[..4],
+            [[1.0, 2.0], [1.0, 5.0], [1.0, 8.0], [2.0, 2.0]]
+        );
+
+        for sampling in [
+            Sampling::LatinHypercube { n: 10, seed: 7 },
+            Sampling::Sobol { n: 10, seed: 7 },
+        ] {
+            sweep.sampling = sampling;
+            let samples = sweep.samples();
+            assert_eq!(samples.len(), 10);
+            assert_eq!(samples, sweep.samples());
+            assert!(samples.iter().all(|s| (1.0..=3.0).contains(&s[0])));
+            assert!(samples.iter().all(|s| (2.0..=8.0).contains(&s[1])));
+        }
+        // each of the 10 strata of each parameter is sampled once
+        sweep.sampling = Sampling::LatinHypercube { n: 10, seed: 7 };
+        let mut strata: Vec<usize> = sweep
+            .samples()
+            .iter()
+            .map(|s| ((s[0] - 1.0) / 2.0 * 10.0) as usize)
+            .collect();
+        strata.sort();
+        assert_eq!(strata, (0..10).collect::<Vec<_>>());
+    }
+}
@@ -0,0 +1,303 @@
+use crate::imports::*;
+use crate::sink::Sink;
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+use serde_json::Value;
+
+/// System that can be run to the end of `t_report`.  Implemented by the
+/// `solver` macro.
+pub trait Simulate: Clone + Send {
+    /// Iterates through time until last value of `t_report`
+    fn walk(&mut self);
+    /// Returns flattened history of the system and its nested fields
+    fn history_columns(&self) -> Columns;
+    /// Returns history sink, if the system has a `#[sink]` field
+    fn sink_mut(&mut self) -> Option<&mut Sink> {
+        None
+    }
+}
+
+/// Applies `f` to every element of `items`, in parallel with the `rayon` feature
+fn map_items<T, R, F>(items: Vec<T>, f: F) -> Vec<R>
+where
+    T: Send,
+    R: Send,
+    F: Fn(T) -> R + Sync + Send,
+{
+    #[cfg(feature = "rayon")]
+    {
+        items.into_par_iter().map(f).collect()
+    }
+    #[cfg(not(feature = "rayon"))]
+    {
+        items.into_iter().map(f).collect()
+    }
+}
+
+/// Returns clone of `base` without its history sink, if any, so that clones
+/// run in parallel do not interleave rows in the same sink
+fn clone_without_sink<S: Simulate>(base: &S) -> S {
+    let mut sys = base.clone();
+    if let Some(sink) = sys.sink_mut() {
+        *sink = Sink::default();
+    }
+    sys
+}
+
+/// Clones `base` once per element of `modifications`, applies `modify` to
+/// each clone, and walks all clones -- in parallel with the `rayon` feature.
+/// Clones share any attached observers, so those should usually be detached
+/// from `base` first, and have no history sink.
+///
+/// # Example
+/// ```ignore
+/// let systems = ensemble(&base, &[1.0, 2.0, 4.0], |sys, c| sys.m1.c = *c);
+/// ```
+pub fn ensemble<S, M, F>(base: &S, modifications: &[M], modify: F) -> Vec<S>
+where
+    S: Simulate + Sync,
+    M: Sync,
+    F: Fn(&mut S, &M) + Sync + Send,
+{
+    map_items(modifications.iter().collect(), |modification| {
+        let mut sys = clone_without_sink(base);
+        modify(&mut sys, modification);
+        sys.walk();
+        sys
+    })
+}
+
+/// Like [ensemble] but returns only the flattened histories
+pub fn ensemble_columns<S, M, F>(base: &S, modifications: &[M], modify: F) -> Vec<Columns>
+where
+    S: Simulate + Sync,
+    M: Sync,
+    F: Fn(&mut S, &M) + Sync + Send,
+{
+    map_items(modifications.iter().collect(), |modification| {
+        let mut sys = clone_without_sink(base);
+        modify(&mut sys, modification);
+        sys.walk();
+        sys.history_columns()
+    })
+}
+
+/// Walks all `systems` -- in parallel with the `rayon` feature.  Fails if
+/// two systems share the same history sink, e.g. because they were cloned
+/// after attaching it, since their rows would be interleaved.
+pub fn walk_all<S: Simulate>(mut systems: Vec<S>) -> anyhow::Result<Vec<S>> {
+    let sinks: Vec<(usize, &Sink)> = systems
+        .iter_mut()
+        .enumerate()
+        .filter_map(|(i, sys)| sys.sink_mut().map(|sink| (i, &*sink)))
+        .filter(|(_, sink)| sink.is_attached())
+        .collect();
+    for (k, (i, sink)) in sinks.iter().enumerate() {
+        if let Some((j, _)) = sinks[k + 1..]
+            .iter()
+            .find(|(_, other)| sink.shares_with(other))
+        {
+            return Err(anyhow!(
+                "Systems {} and {} share the same history sink",
+                i,
+                j
+            ));
+        }
+    }
+    Ok(map_items(systems, |mut sys| {
+        sys.walk();
+        sys
+    }))
+}
+
+/// Applies JSON merge patch (RFC 7386) `patch` to `target`.  Objects are
+/// merged recursively, `null` removes a key, and anything else replaces.
+pub fn merge_patch(target: &mut Value, patch: &Value) {
+    match patch {
+        Value::Object(patch) => {
+            if !target.is_object() {
+                *target = Value::Object(Default::default());
+            }
+            let target = target.as_object_mut().unwrap();
+            for (key, value) in patch {
+                if value.is_null() {
+                    target.remove(key);
+                } else {
+                    merge_patch(target.entry(key.clone()).or_insert(Value::Null), value);
+                }
+            }
+        }
+        _ => *target = patch.clone(),
+    }
+}
+
+/// Fails if `base` contains a NaN or infinite number, which JSON cannot
+/// represent and would silently turn into `null`
+fn check_finite<S: Serialize>(base: &S) -> anyhow::Result<()> {
+    fn check(value: &serde_yaml::Value, path: &str) -> anyhow::Result<()> {
+        let join = |key: &dyn std::fmt::Display| {
+            if path.is_empty() {
+                key.to_string()
+            } else {
+                format!("{}.{}", path, key)
+            }
+        };
+        match value {
+            serde_yaml::Value::Number(x) if x.is_nan() || x.is_infinite() => Err(anyhow!(
+                "`{}` is {}, which cannot be represented in JSON",
+                path,
+                x
+            )),
+            serde_yaml::Value::Sequence(items) => items
+                .iter()
+                .enumerate()
+                .try_for_each(|(i, item)| check(item, &join(&i))),
+            serde_yaml::Value::Mapping(map) => map.iter().try_for_each(|(key, item)| {
+                let key = key
+                    .as_str()
+                    .map(str::to_string)
+                    .unwrap_or_else(|| format!("{:?}", key));
+                check(item, &join(&key))
+            }),
+            serde_yaml::Value::Tagged(tagged) => check(&tagged.value, path),
+            _ => Ok(()),
+        }
+    }
+    check(&serde_yaml::to_value(base)?, "")
+}
+
+/// Returns copy of `base` with JSON merge patch `patch` applied, e.g.
+/// `{"m1": {"c": 2.0}}`.  The copy is made through JSON, so fields skipped
+/// by serde -- attached observers and sink, and the snapshot -- are reset
+/// to default, while closures, e.g. of a
+/// [FlowSource](crate::components::FlowSource), and NaN or infinite numbers
+/// in `base` are an error.
+pub fn with_patch<S: SerdeAPI>(base: &S, patch: &Value) -> anyhow::Result<S> {
+    check_finite(base)?;
+    let mut value = serde_json::to_value(base)?;
+    merge_patch(&mut value, patch);
+    Ok(serde_json::from_value(value)?)
+}
+
+/// Returns one copy of `base` per JSON merge patch in `patches`
+pub fn with_patches<S: SerdeAPI>(base: &S, patches: &[String]) -> anyhow::Result<Vec<S>> {
+    patches
+        .iter()
+        .map(|patch| with_patch(base, &serde_json::from_str(patch)?))
+        .collect()
+}
+
+/// Converts `path` like `m2.c` to JSON pointer `/m2/c`.  Paths that already
+/// start with `/` are returned unchanged.
+pub fn json_pointer(path: &str) -> String {
+    if path.starts_with('/') {
+        path.to_string()
+    } else {
+        format!("/{}", path.replace('.', "/"))
+    }
+}
+
+/// Sets number at `path` (see [json_pointer]) in serialized system `value`.
+/// Fails if `path` does not point to an existing number, `x` is not finite,
+/// since JSON would turn it into `null`, or the number is an integer and `x`
+/// is not.
+pub fn set_path(value: &mut Value, path: &str, x: f64) -> anyhow::Result<()> {
+    if !x.is_finite() {
+        return Err(anyhow!("`{}` cannot be set to {}", path, x));
+    }
+    match value.pointer_mut(&json_pointer(path)) {
+        // positive values of signed fields are serialized as `u64`
+        Some(target) if target.is_u64() || target.is_i64() => {
+            if x.fract() != 0.0 {
+                return Err(anyhow!(
+                    "`{}` is an integer and cannot be set to {}",
+                    path,
+                    x
+                ));
+            }
+            *target = if x < 0.0 {
+                serde_json::json!(x as i64)
+            } else {
+                serde_json::json!(x as u64)
+            };
+            Ok(())
+        }
+        Some(target) if target.is_number() => {
+            *target = serde_json::json!(x);
+            Ok(())
+        }
+        Some(_) => Err(anyhow!("`{}` is not a number", path)),
+        None => Err(anyhow!("`{}` does not exist", path)),
+    }
+}
+
+/// Returns one copy of `base` per element of `samples`, with the numbers at
+/// `paths` (see [json_pointer]) set to the values of that sample.  Like
+/// [with_patch], fields skipped by serde are reset to default, and NaN or
+/// infinite numbers are an error.
+pub fn with_path_values<S: SerdeAPI>(
+    base: &S,
+    paths: &[&str],
+    samples: &[Vec<f64>],
+) -> anyhow::Result<Vec<S>> {
+    check_finite(base)?;
+    let value = serde_json::to_value(base)?;
+    samples
+        .iter()
+        .map(|sample| {
+            let mut value = value.clone();
+            for (path, x) in paths.iter().zip(sample) {
+                set_path(&mut value, path, *x)?;
+            }
+            // e.g. integer out of range of its field
+            serde_json::from_value(value)
+                .map_err(|err| anyhow!("setting {:?} to {:?}: {}", paths, sample, err))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SystemState;
+
+    #[test]
+    fn test_patches_and_paths() {
+        let mut target = serde_json::json!({"m1": {"c": 1.0, "h": 2.0}, "t": [0.0, 1.0]});
+        merge_patch(
+            &mut target,
+            &serde_json::json!({"m1": {"c": 3.0, "h": null}}),
+        );
+        assert_eq!(
+            target,
+            serde_json::json!({"m1": {"c": 3.0}, "t": [0.0, 1.0]})
+        );
+
+        assert_eq!(json_pointer("m1.c"), "/m1/c");
+        assert_eq!(json_pointer("/t/1"), "/t/1");
+        set_path(&mut target, "t.1", 2.0).unwrap();
+        assert_eq!(target["t"][1], 2.0);
+        assert!(set_path(&mut target, "m1", 2.0).is_err());
+        assert!(set_path(&mut target, "m2.c", 2.0).is_err());
+        assert!(set_path(&mut target, "m1.c", f64::NAN).is_err());
+        let mut target = serde_json::json!({"n": 3, "k": -1});
+        set_path(&mut target, "n", 4.0).unwrap();
+        set_path(&mut target, "k", -2.0).unwrap();
+        assert_eq!(target, serde_json::json!({"n": 4, "k": -2}));
+        assert!(target["n"].is_u64());
+        let err = set_path(&mut target, "n", 4.5).unwrap_err();
+        assert!(err.to_string().contains("`n`"));
+
+        let state = SystemState { i: 0, time: 0.5 };
+        let patched = with_patch(&state, &serde_json::json!({"time": 1.5})).unwrap();
+        assert_eq!(patched.time, 1.5);
+        let err = with_patch(
+            &SystemState {
+                i: 0,
+                time: f64::INFINITY,
+            },
+            &Value::Null,
+        );
+        assert!(err.is_err());
+    }
+}
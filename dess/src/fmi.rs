@@ -0,0 +1,792 @@
+//! Export of `#[solver]` systems as FMI 2.0 Co-Simulation FMUs.
+//!
+//! A system implements [FmiModel] via [impl_fmi_model](crate::impl_fmi_model)
+//! in the crate that defines it, and a `cdylib` crate then exports the
+//! `fmi2*` C functions for it via [export_fmi2](crate::export_fmi2).
+//! See the `dess-examples-fmi` crate for a template.
+
+use crate::imports::*;
+use std::ffi::{c_char, c_void, CStr};
+
+pub type Fmi2Status = i32;
+pub const FMI2_OK: Fmi2Status = 0;
+pub const FMI2_WARNING: Fmi2Status = 1;
+pub const FMI2_DISCARD: Fmi2Status = 2;
+pub const FMI2_ERROR: Fmi2Status = 3;
+pub const FMI2_FATAL: Fmi2Status = 4;
+pub const FMI2_PENDING: Fmi2Status = 5;
+
+/// `fmi2Type` value for co-simulation
+pub const FMI2_COSIMULATION: i32 = 1;
+
+/// Causality of an FMI scalar variable, written to `modelDescription.xml`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FmiCausality {
+    /// simulation time
+    Independent,
+    /// component parameter, settable between steps
+    Parameter,
+    /// state of a `#[use_state]` field, settable only until
+    /// `fmi2ExitInitializationMode`
+    State,
+    /// numeric field of a nested `state`, read only
+    Output,
+}
+
+/// FMI scalar variable whose value reference is its index in
+/// [FmiModel::fmi_variables]
+#[derive(Clone, Debug, PartialEq)]
+pub struct FmiVariable {
+    pub name: String,
+    pub causality: FmiCausality,
+}
+
+/// Component parameter exposed to FMI by [impl_fmi_model](crate::impl_fmi_model)
+pub struct FmiParameter<M> {
+    pub name: &'static str,
+    pub get: fn(&M) -> f64,
+    pub set: fn(&mut M, f64),
+}
+
+/// System that can be exported as an FMI 2.0 Co-Simulation FMU.
+/// Value references are ordered as `time`, states, parameters, then outputs.
+pub trait FmiModel: HasStates + Default {
+    /// Returns names of states in the order of [HasStates::states]
+    fn fmi_state_names(&self) -> Vec<String>;
+    /// Returns component parameters
+    fn fmi_parameters() -> Vec<FmiParameter<Self>>;
+    /// Returns names of outputs
+    fn fmi_output_names(&self) -> Vec<String>;
+    /// Returns values of outputs in the order of [Self::fmi_output_names]
+    fn fmi_output_values(&self) -> Vec<f64>;
+    /// Returns current time
+    fn fmi_time(&self) -> f64;
+    /// Sets current time, e.g. at start of experiment
+    fn fmi_set_time(&mut self, t: f64);
+    /// Advances system to time `t`, keeping no history beyond the current
+    /// state so that memory does not grow over a long co-simulation
+    fn fmi_advance_to(&mut self, t: f64);
+
+    /// Returns all scalar variables
+    fn fmi_variables(&self) -> Vec<FmiVariable> {
+        let mut vars = vec![FmiVariable {
+            name: "time".into(),
+            causality: FmiCausality::Independent,
+        }];
+        vars.extend(self.fmi_state_names().iter().map(|name| FmiVariable {
+            name: format!("{}.state", name),
+            causality: FmiCausality::State,
+        }));
+        vars.extend(Self::fmi_parameters().iter().map(|p| FmiVariable {
+            name: p.name.into(),
+            causality: FmiCausality::Parameter,
+        }));
+        vars.extend(self.fmi_output_names().into_iter().map(|name| FmiVariable {
+            name,
+            causality: FmiCausality::Output,
+        }));
+        vars
+    }
+
+    /// Returns value of variable with value reference `vr`, if it exists
+    fn fmi_get_real(&self, vr: usize) -> Option<f64> {
+        if vr == 0 {
+            return Some(self.fmi_time());
+        }
+        let mut idx = vr - 1;
+        let states = self.states();
+        if idx < states.len() {
+            return Some(states[idx]);
+        }
+        idx -= states.len();
+        let params = Self::fmi_parameters();
+        if idx < params.len() {
+            return Some((params[idx].get)(self));
+        }
+        idx -= params.len();
+        self.fmi_output_values().get(idx).copied()
+    }
+
+    /// Sets value of state or parameter with value reference `vr`
+    fn fmi_set_real(&mut self, vr: usize, value: f64) -> anyhow::Result<()> {
+        let n_states = self.fmi_state_names().len();
+        let params = Self::fmi_parameters();
+        if vr == 0 {
+            Err(anyhow!("`time` cannot be set"))
+        } else if vr <= n_states {
+            let mut states = self.states();
+            states[vr - 1] = value;
+            self.set_states(states);
+            Ok(())
+        } else if vr <= n_states + params.len() {
+            (params[vr - 1 - n_states].set)(self, value);
+            Ok(())
+        } else {
+            Err(anyhow!("Value reference {} cannot be set", vr))
+        }
+    }
+}
+
+/// Returns `modelDescription.xml` contents for `M`, with start values of
+/// states and parameters taken from `M::default()`
+pub fn model_description<M: FmiModel>(model_identifier: &str, guid: &str) -> String {
+    let model = M::default();
+    let vars = model.fmi_variables();
+    let mut xml = format!(
+        concat!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n",
+            "<fmiModelDescription fmiVersion=\"2.0\" modelName=\"{id}\" guid=\"{guid}\" ",
+            "generationTool=\"dess\" variableNamingConvention=\"flat\" ",
+            "numberOfEventIndicators=\"0\">\n",
+            "  <CoSimulation modelIdentifier=\"{id}\" ",
+            "canHandleVariableCommunicationStepSize=\"true\"/>\n",
+            "  <ModelVariables>\n"
+        ),
+        id = model_identifier,
+        guid = guid
+    );
+    for (vr, var) in vars.iter().enumerate() {
+        let (attrs, start) = match var.causality {
+            FmiCausality::Independent => {
+                ("causality=\"independent\" variability=\"continuous\"", None)
+            }
+            FmiCausality::Parameter => (
+                "causality=\"parameter\" variability=\"tunable\" initial=\"exact\"",
+                model.fmi_get_real(vr),
+            ),
+            FmiCausality::State => (
+                "causality=\"local\" variability=\"continuous\" initial=\"exact\"",
+                model.fmi_get_real(vr),
+            ),
+            FmiCausality::Output => (
+                "causality=\"output\" variability=\"continuous\" initial=\"calculated\"",
+                None,
+            ),
+        };
+        let real = match start {
+            Some(start) => format!("<Real start=\"{}\"/>", start),
+            None => "<Real/>".to_string(),
+        };
+        xml.push_str(&format!(
+            "    <ScalarVariable name=\"{}\" valueReference=\"{}\" {}>\n      {}\n    </ScalarVariable>\n",
+            var.name, vr, attrs, real
+        ));
+    }
+    xml.push_str("  </ModelVariables>\n  <ModelStructure>\n    <Outputs>\n");
+    // `index` is one-based position in `ModelVariables`
+    let output_indices: Vec<usize> = vars
+        .iter()
+        .enumerate()
+        .filter(|(_, var)| var.causality == FmiCausality::Output)
+        .map(|(vr, _)| vr + 1)
+        .collect();
+    for index in &output_indices {
+        xml.push_str(&format!("      <Unknown index=\"{}\"/>\n", index));
+    }
+    xml.push_str("    </Outputs>\n    <InitialUnknowns>\n");
+    for index in &output_indices {
+        xml.push_str(&format!("      <Unknown index=\"{}\"/>\n", index));
+    }
+    xml.push_str("    </InitialUnknowns>\n  </ModelStructure>\n</fmiModelDescription>\n");
+    xml
+}
+
+/// Instance of `M` behind an `fmi2Component` pointer
+pub struct FmiInstance<M: FmiModel> {
+    pub instance_name: String,
+    pub model: M,
+    /// whether `fmi2ExitInitializationMode` has been called, after which
+    /// states can no longer be set
+    pub initialized: bool,
+}
+
+/// Converts C string to `String`, treating null as empty
+unsafe fn c_str(s: *const c_char) -> String {
+    if s.is_null() {
+        String::new()
+    } else {
+        CStr::from_ptr(s).to_string_lossy().into_owned()
+    }
+}
+
+/// Implements `fmi2Instantiate`.  Returns null if `fmu_type` is not
+/// co-simulation or `fmu_guid` does not match `guid`.
+///
+/// # Safety
+/// `instance_name` and `fmu_guid` must be null or valid C strings.
+pub unsafe fn instantiate<M: FmiModel>(
+    instance_name: *const c_char,
+    fmu_type: i32,
+    fmu_guid: *const c_char,
+    guid: &str,
+) -> *mut c_void {
+    if fmu_type != FMI2_COSIMULATION || c_str(fmu_guid) != guid {
+        return std::ptr::null_mut();
+    }
+    Box::into_raw(Box::new(FmiInstance {
+        instance_name: c_str(instance_name),
+        model: M::default(),
+        initialized: false,
+    })) as *mut c_void
+}
+
+/// Implements `fmi2FreeInstance`
+///
+/// # Safety
+/// `c` must be null or returned by [instantiate] with the same `M` and not yet freed.
+pub unsafe fn free_instance<M: FmiModel>(c: *mut c_void) {
+    if !c.is_null() {
+        drop(Box::from_raw(c as *mut FmiInstance<M>));
+    }
+}
+
+/// Returns instance behind `c`, if not null
+///
+/// # Safety
+/// `c` must be null or returned by [instantiate] with the same `M` and not yet freed.
+pub unsafe fn instance<'a, M: FmiModel>(c: *mut c_void) -> Option<&'a mut FmiInstance<M>> {
+    (c as *mut FmiInstance<M>).as_mut()
+}
+
+/// Implements `fmi2SetupExperiment`
+///
+/// # Safety
+/// See [instance].
+pub unsafe fn setup_experiment<M: FmiModel>(c: *mut c_void, start_time: f64) -> Fmi2Status {
+    match instance::<M>(c) {
+        Some(inst) => {
+            inst.model.fmi_set_time(start_time);
+            FMI2_OK
+        }
+        None => FMI2_ERROR,
+    }
+}
+
+/// Implements `fmi2ExitInitializationMode`
+///
+/// # Safety
+/// See [instance].
+pub unsafe fn exit_initialization_mode<M: FmiModel>(c: *mut c_void) -> Fmi2Status {
+    match instance::<M>(c) {
+        Some(inst) => {
+            inst.initialized = true;
+            FMI2_OK
+        }
+        None => FMI2_ERROR,
+    }
+}
+
+/// Implements `fmi2Reset` by replacing the model with `M::default()`
+///
+/// # Safety
+/// See [instance].
+pub unsafe fn reset<M: FmiModel>(c: *mut c_void) -> Fmi2Status {
+    match instance::<M>(c) {
+        Some(inst) => {
+            inst.model = M::default();
+            inst.initialized = false;
+            FMI2_OK
+        }
+        None => FMI2_ERROR,
+    }
+}
+
+/// Implements `fmi2GetReal`
+///
+/// # Safety
+/// See [instance].  `vr` and `value` must point to `nvr` elements.
+pub unsafe fn get_real<M: FmiModel>(
+    c: *mut c_void,
+    vr: *const u32,
+    nvr: usize,
+    value: *mut f64,
+) -> Fmi2Status {
+    let Some(inst) = instance::<M>(c) else {
+        return FMI2_ERROR;
+    };
+    if nvr == 0 {
+        return FMI2_OK;
+    }
+    let vr = std::slice::from_raw_parts(vr, nvr);
+    let value = std::slice::from_raw_parts_mut(value, nvr);
+    for (v, r) in value.iter_mut().zip(vr) {
+        match inst.model.fmi_get_real(*r as usize) {
+            Some(x) => *v = x,
+            None => return FMI2_ERROR,
+        }
+    }
+    FMI2_OK
+}
+
+/// Implements `fmi2SetReal`.  States are `local` variables in
+/// `modelDescription.xml` and so are rejected once initialized.
+///
+/// # Safety
+/// See [instance].  `vr` and `value` must point to `nvr` elements.
+pub unsafe fn set_real<M: FmiModel>(
+    c: *mut c_void,
+    vr: *const u32,
+    nvr: usize,
+    value: *const f64,
+) -> Fmi2Status {
+    let Some(inst) = instance::<M>(c) else {
+        return FMI2_ERROR;
+    };
+    if nvr == 0 {
+        return FMI2_OK;
+    }
+    let vr = std::slice::from_raw_parts(vr, nvr);
+    let value = std::slice::from_raw_parts(value, nvr);
+    let n_states = inst.model.fmi_state_names().len();
+    for (r, v) in vr.iter().zip(value) {
+        let is_state = (1..=n_states).contains(&(*r as usize));
+        if (inst.initialized && is_state) || inst.model.fmi_set_real(*r as usize, *v).is_err() {
+            return FMI2_ERROR;
+        }
+    }
+    FMI2_OK
+}
+
+/// Implements `fmi2DoStep` by advancing to
+/// `current_communication_point + communication_step_size`.  Returns
+/// `fmi2Error` before `fmi2ExitInitializationMode` or if
+/// `current_communication_point` is not the model time, and `fmi2Fatal` if
+/// the model panics, after which the instance must only be freed.
+///
+/// # Safety
+/// See [instance].
+pub unsafe fn do_step<M: FmiModel>(
+    c: *mut c_void,
+    current_communication_point: f64,
+    communication_step_size: f64,
+) -> Fmi2Status {
+    let Some(inst) = instance::<M>(c) else {
+        return FMI2_ERROR;
+    };
+    let time = inst.model.fmi_time();
+    // communication points accumulated by the master may differ from the
+    // model time by rounding
+    let tol = 1e-9 * time.abs().max(communication_step_size.abs()).max(1.0);
+    if !inst.initialized
+        || !communication_step_size.is_finite()
+        || communication_step_size < 0.0
+        || (current_communication_point - time).abs() > tol
+    {
+        return FMI2_ERROR;
+    }
+    // unwinding across the C boundary is undefined behavior
+    match std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        inst.model
+            .fmi_advance_to(current_communication_point + communication_step_size)
+    })) {
+        Ok(()) => FMI2_OK,
+        Err(_) => FMI2_FATAL,
+    }
+}
+
+/// Implements [FmiModel] for a `#[solver]` system, exposing its states,
+/// the numeric fields of nested states as outputs, and the listed
+/// component parameters, e.g.
+/// `impl_fmi_model!(System3TM, parameters: [m1.c, h12.h]);`
+#[macro_export]
+macro_rules! impl_fmi_model {
+    ($sys: ty, parameters: [$($p0: ident $(.$p: ident)*),* $(,)?]) => {
+        impl $crate::fmi::FmiModel for $sys {
+            fn fmi_state_names(&self) -> Vec<String> {
+                $crate::traits_and_macros::HasStates::state_names(self)
+            }
+            fn fmi_parameters() -> Vec<$crate::fmi::FmiParameter<Self>> {
+                vec![$(
+                    $crate::fmi::FmiParameter {
+                        name: concat!(stringify!($p0) $(, ".", stringify!($p))*),
+                        get: |sys: &Self| sys.$p0$(.$p)*,
+                        set: |sys: &mut Self, value: f64| sys.$p0$(.$p)* = value,
+                    }
+                ),*]
+            }
+            fn fmi_output_names(&self) -> Vec<String> {
+                self.state_column_names()
+                    .into_iter()
+                    .filter(|name| name != "i" && name != "time")
+                    .collect()
+            }
+            fn fmi_output_values(&self) -> Vec<f64> {
+                self.state_column_names()
+                    .iter()
+                    .zip(self.state_column_values())
+                    .filter(|(name, _)| *name != "i" && *name != "time")
+                    .map(|(_, value)| value)
+                    .collect()
+            }
+            fn fmi_time(&self) -> f64 {
+                self.state.time
+            }
+            fn fmi_set_time(&mut self, t: f64) {
+                self.state.time = t;
+            }
+            fn fmi_advance_to(&mut self, t: f64) {
+                self.advance_to(t);
+                // values are only ever read at the current time
+                self.keep_last(1);
+                if let Some(sc) = $crate::solver::SolverBase::sc_mut(self) {
+                    sc.history.keep_last(1);
+                }
+            }
+        }
+    };
+}
+
+/// Exports `fmi2*` C functions for co-simulation of `$sys`, which must
+/// implement [FmiModel], and a `model_description` function returning
+/// `modelDescription.xml` contents.  Must be invoked at most once per `cdylib`.
+/// `$id` must match the shared library name and `$guid` the `guid` in
+/// `modelDescription.xml`.
+#[macro_export]
+macro_rules! export_fmi2 {
+    ($sys: ty, model_identifier: $id: expr, guid: $guid: expr $(,)?) => {
+        mod fmi2_export {
+            #![allow(non_snake_case, clippy::missing_safety_doc)]
+            use super::*;
+            use std::ffi::{c_char, c_void};
+            use $crate::fmi::*;
+
+            /// Returns `modelDescription.xml` contents
+            pub fn model_description() -> String {
+                $crate::fmi::model_description::<$sys>($id, $guid)
+            }
+
+            #[no_mangle]
+            pub extern "C" fn fmi2GetTypesPlatform() -> *const c_char {
+                b"default\0".as_ptr() as *const c_char
+            }
+
+            #[no_mangle]
+            pub extern "C" fn fmi2GetVersion() -> *const c_char {
+                b"2.0\0".as_ptr() as *const c_char
+            }
+
+            #[no_mangle]
+            pub unsafe extern "C" fn fmi2SetDebugLogging(
+                _c: *mut c_void,
+                _logging_on: i32,
+                _n_categories: usize,
+                _categories: *const *const c_char,
+            ) -> Fmi2Status {
+                FMI2_OK
+            }
+
+            #[no_mangle]
+            pub unsafe extern "C" fn fmi2Instantiate(
+                instance_name: *const c_char,
+                fmu_type: i32,
+                fmu_guid: *const c_char,
+                _fmu_resource_location: *const c_char,
+                _functions: *const c_void,
+                _visible: i32,
+                _logging_on: i32,
+            ) -> *mut c_void {
+                instantiate::<$sys>(instance_name, fmu_type, fmu_guid, $guid)
+            }
+
+            #[no_mangle]
+            pub unsafe extern "C" fn fmi2FreeInstance(c: *mut c_void) {
+                free_instance::<$sys>(c)
+            }
+
+            #[no_mangle]
+            pub unsafe extern "C" fn fmi2SetupExperiment(
+                c: *mut c_void,
+                _tolerance_defined: i32,
+                _tolerance: f64,
+                start_time: f64,
+                _stop_time_defined: i32,
+                _stop_time: f64,
+            ) -> Fmi2Status {
+                setup_experiment::<$sys>(c, start_time)
+            }
+
+            #[no_mangle]
+            pub unsafe extern "C" fn fmi2EnterInitializationMode(c: *mut c_void) -> Fmi2Status {
+                if c.is_null() {
+                    FMI2_ERROR
+                } else {
+                    FMI2_OK
+                }
+            }
+
+            #[no_mangle]
+            pub unsafe extern "C" fn fmi2ExitInitializationMode(c: *mut c_void) -> Fmi2Status {
+                exit_initialization_mode::<$sys>(c)
+            }
+
+            #[no_mangle]
+            pub unsafe extern "C" fn fmi2Terminate(c: *mut c_void) -> Fmi2Status {
+                if c.is_null() {
+                    FMI2_ERROR
+                } else {
+                    FMI2_OK
+                }
+            }
+
+            #[no_mangle]
+            pub unsafe extern "C" fn fmi2Reset(c: *mut c_void) -> Fmi2Status {
+                reset::<$sys>(c)
+            }
+
+            #[no_mangle]
+            pub unsafe extern "C" fn fmi2GetReal(
+                c: *mut c_void,
+                vr: *const u32,
+                nvr: usize,
+                value: *mut f64,
+            ) -> Fmi2Status {
+                get_real::<$sys>(c, vr, nvr, value)
+            }
+
+            #[no_mangle]
+            pub unsafe extern "C" fn fmi2SetReal(
+                c: *mut c_void,
+                vr: *const u32,
+                nvr: usize,
+                value: *const f64,
+            ) -> Fmi2Status {
+                set_real::<$sys>(c, vr, nvr, value)
+            }
+
+            #[no_mangle]
+            pub unsafe extern "C" fn fmi2DoStep(
+                c: *mut c_void,
+                current_communication_point: f64,
+                communication_step_size: f64,
+                _no_set_fmu_state_prior_to_current_point: i32,
+            ) -> Fmi2Status {
+                do_step::<$sys>(c, current_communication_point, communication_step_size)
+            }
+
+            #[no_mangle]
+            pub unsafe extern "C" fn fmi2CancelStep(_c: *mut c_void) -> Fmi2Status {
+                FMI2_ERROR
+            }
+
+            // there are no integer, boolean, or string variables
+            #[no_mangle]
+            pub unsafe extern "C" fn fmi2GetInteger(
+                _c: *mut c_void,
+                _vr: *const u32,
+                nvr: usize,
+                _value: *mut i32,
+            ) -> Fmi2Status {
+                if nvr == 0 {
+                    FMI2_OK
+                } else {
+                    FMI2_ERROR
+                }
+            }
+
+            #[no_mangle]
+            pub unsafe extern "C" fn fmi2SetInteger(
+                _c: *mut c_void,
+                _vr: *const u32,
+                nvr: usize,
+                _value: *const i32,
+            ) -> Fmi2Status {
+                if nvr == 0 {
+                    FMI2_OK
+                } else {
+                    FMI2_ERROR
+                }
+            }
+
+            #[no_mangle]
+            pub unsafe extern "C" fn fmi2GetBoolean(
+                _c: *mut c_void,
+                _vr: *const u32,
+                nvr: usize,
+                _value: *mut i32,
+            ) -> Fmi2Status {
+                if nvr == 0 {
+                    FMI2_OK
+                } else {
+                    FMI2_ERROR
+                }
+            }
+
+            #[no_mangle]
+            pub unsafe extern "C" fn fmi2SetBoolean(
+                _c: *mut c_void,
+                _vr: *const u32,
+                nvr: usize,
+                _value: *const i32,
+            ) -> Fmi2Status {
+                if nvr == 0 {
+                    FMI2_OK
+                } else {
+                    FMI2_ERROR
+                }
+            }
+
+            #[no_mangle]
+            pub unsafe extern "C" fn fmi2GetString(
+                _c: *mut c_void,
+                _vr: *const u32,
+                nvr: usize,
+                _value: *mut *const c_char,
+            ) -> Fmi2Status {
+                if nvr == 0 {
+                    FMI2_OK
+                } else {
+                    FMI2_ERROR
+                }
+            }
+
+            #[no_mangle]
+            pub unsafe extern "C" fn fmi2SetString(
+                _c: *mut c_void,
+                _vr: *const u32,
+                nvr: usize,
+                _value: *const *const c_char,
+            ) -> Fmi2Status {
+                if nvr == 0 {
+                    FMI2_OK
+                } else {
+                    FMI2_ERROR
+                }
+            }
+
+            // optional capabilities not declared in `modelDescription.xml`
+            #[no_mangle]
+            pub unsafe extern "C" fn fmi2GetFMUstate(
+                _c: *mut c_void,
+                _state: *mut *mut c_void,
+            ) -> Fmi2Status {
+                FMI2_ERROR
+            }
+
+            #[no_mangle]
+            pub unsafe extern "C" fn fmi2SetFMUstate(
+                _c: *mut c_void,
+                _state: *mut c_void,
+            ) -> Fmi2Status {
+                FMI2_ERROR
+            }
+
+            #[no_mangle]
+            pub unsafe extern "C" fn fmi2FreeFMUstate(
+                _c: *mut c_void,
+                _state: *mut *mut c_void,
+            ) -> Fmi2Status {
+                FMI2_ERROR
+            }
+
+            #[no_mangle]
+            pub unsafe extern "C" fn fmi2SerializedFMUstateSize(
+                _c: *mut c_void,
+                _state: *mut c_void,
+                _size: *mut usize,
+            ) -> Fmi2Status {
+                FMI2_ERROR
+            }
+
+            #[no_mangle]
+            pub unsafe extern "C" fn fmi2SerializeFMUstate(
+                _c: *mut c_void,
+                _state: *mut c_void,
+                _serialized: *mut c_char,
+                _size: usize,
+            ) -> Fmi2Status {
+                FMI2_ERROR
+            }
+
+            #[no_mangle]
+            pub unsafe extern "C" fn fmi2DeSerializeFMUstate(
+                _c: *mut c_void,
+                _serialized: *const c_char,
+                _size: usize,
+                _state: *mut *mut c_void,
+            ) -> Fmi2Status {
+                FMI2_ERROR
+            }
+
+            #[no_mangle]
+            pub unsafe extern "C" fn fmi2GetDirectionalDerivative(
+                _c: *mut c_void,
+                _v_unknown_ref: *const u32,
+                _n_unknown: usize,
+                _v_known_ref: *const u32,
+                _n_known: usize,
+                _dv_known: *const f64,
+                _dv_unknown: *mut f64,
+            ) -> Fmi2Status {
+                FMI2_ERROR
+            }
+
+            #[no_mangle]
+            pub unsafe extern "C" fn fmi2SetRealInputDerivatives(
+                _c: *mut c_void,
+                _vr: *const u32,
+                _nvr: usize,
+                _order: *const i32,
+                _value: *const f64,
+            ) -> Fmi2Status {
+                FMI2_ERROR
+            }
+
+            #[no_mangle]
+            pub unsafe extern "C" fn fmi2GetRealOutputDerivatives(
+                _c: *mut c_void,
+                _vr: *const u32,
+                _nvr: usize,
+                _order: *const i32,
+                _value: *mut f64,
+            ) -> Fmi2Status {
+                FMI2_ERROR
+            }
+
+            #[no_mangle]
+            pub unsafe extern "C" fn fmi2GetStatus(
+                _c: *mut c_void,
+                _kind: i32,
+                _value: *mut i32,
+            ) -> Fmi2Status {
+                FMI2_DISCARD
+            }
+
+            #[no_mangle]
+            pub unsafe extern "C" fn fmi2GetRealStatus(
+                _c: *mut c_void,
+                _kind: i32,
+                _value: *mut f64,
+            ) -> Fmi2Status {
+                FMI2_DISCARD
+            }
+
+            #[no_mangle]
+            pub unsafe extern "C" fn fmi2GetIntegerStatus(
+                _c: *mut c_void,
+                _kind: i32,
+                _value: *mut i32,
+            ) -> Fmi2Status {
+                FMI2_DISCARD
+            }
+
+            #[no_mangle]
+            pub unsafe extern "C" fn fmi2GetBooleanStatus(
+                _c: *mut c_void,
+                _kind: i32,
+                _value: *mut i32,
+            ) -> Fmi2Status {
+                FMI2_DISCARD
+            }
+
+            #[no_mangle]
+            pub unsafe extern "C" fn fmi2GetStringStatus(
+                _c: *mut c_void,
+                _kind: i32,
+                _value: *mut *const c_char,
+            ) -> Fmi2Status {
+                FMI2_DISCARD
+            }
+        }
+        pub use fmi2_export::*;
+    };
+}
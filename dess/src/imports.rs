@@ -1,5 +1,10 @@
+pub(crate) use crate::columns::*;
+#[cfg(any(feature = "doe", feature = "monte_carlo"))]
+pub(crate) use crate::ensemble::*;
+pub(crate) use crate::observer::*;
 #[cfg(feature = "pyo3")]
 pub use crate::pyo3_imports::*;
+pub(crate) use crate::retention::*;
 #[allow(unused)]
 pub(crate) use crate::traits_and_macros::*;
 pub(crate) use crate::zip;
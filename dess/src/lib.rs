@@ -1,11 +1,33 @@
+// lets code generated by `dess-proc-macros` refer to `dess::` inside this crate
+extern crate self as dess;
+
+#[cfg(feature = "arrow")]
+pub mod arrow;
+pub mod columns;
+pub mod components;
+#[cfg(feature = "doe")]
+pub mod doe;
+pub mod ensemble;
+pub mod fmi;
 mod imports;
+#[cfg(feature = "monte_carlo")]
+pub mod monte_carlo;
+pub mod network;
+pub mod observer;
 pub mod prelude;
 #[cfg(feature = "pyo3")]
 pub(crate) mod pyo3_imports;
+pub mod retention;
+pub mod signal;
+pub mod sink;
+pub mod snapshot;
 pub mod solver;
+pub mod subsystem;
+pub mod time_series;
 pub mod traits_and_macros;
 pub mod utilities;
 
+pub use anyhow;
 use prelude::*;
 use serde::{Deserialize, Serialize};
 
@@ -13,7 +35,8 @@ use serde::{Deserialize, Serialize};
 #[common_derives]
 #[pyo3_api]
 pub struct SystemState {
-    // current index in `t_report`
+    // number of states saved so far, which is also the index in `t_report`
+    // of the next report time as long as only `walk` is used
     pub i: usize,
     // current time
     pub time: f64,
@@ -0,0 +1,187 @@
+use crate::imports::*;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use rand_distr::Distribution as _;
+
+/// Probability distribution of an uncertain parameter
+#[common_derives]
+pub enum Distribution {
+    Normal {
+        mean: f64,
+        std_dev: f64,
+    },
+    Uniform {
+        min: f64,
+        max: f64,
+    },
+    /// distribution whose natural log is normal with mean `mu` and
+    /// standard deviation `sigma`
+    LogNormal {
+        mu: f64,
+        sigma: f64,
+    },
+}
+
+impl Distribution {
+    /// Draws one sample from distribution using `rng`
+    pub fn sample(&self, rng: &mut StdRng) -> anyhow::Result<f64> {
+        Ok(match self {
+            Distribution::Normal { mean, std_dev } => {
+                rand_distr::Normal::new(*mean, *std_dev)?.sample(rng)
+            }
+            Distribution::Uniform { min, max } => {
+                if min > max {
+                    return Err(anyhow!("Uniform `min` {} exceeds `max` {}", min, max));
+                }
+                rand_distr::Uniform::new_inclusive(*min, *max).sample(rng)
+            }
+            Distribution::LogNormal { mu, sigma } => {
+                rand_distr::LogNormal::new(*mu, *sigma)?.sample(rng)
+            }
+        })
+    }
+}
+
+/// Parameter at `path` like `m1.c` or JSON pointer `/m1/c` with uncertain value
+#[common_derives]
+pub struct UncertainParameter {
+    pub path: String,
+    pub distribution: Distribution,
+}
+
+fn default_percentiles() -> Vec<f64> {
+    vec![5.0, 50.0, 95.0]
+}
+
+/// Monte Carlo propagation of parameter uncertainty to history columns.
+/// Can be read from YAML, e.g.
+/// ```yaml
+/// parameters:
+///   - {path: m1.c, distribution: !Normal {mean: 1.0, std_dev: 0.1}}
+///   - {path: h12.h, distribution: !Uniform {min: 4.0, max: 6.0}}
+///   - {path: m2.c, distribution: !LogNormal {mu: 0.7, sigma: 0.1}}
+/// n: 100
+/// seed: 42
+/// percentiles: [5, 50, 95]
+/// ```
+#[common_derives]
+pub struct MonteCarlo {
+    pub parameters: Vec<UncertainParameter>,
+    /// number of runs
+    pub n: usize,
+    /// seed for reproducible sampling
+    pub seed: u64,
+    /// percentiles in \[0, 100\] returned by [Self::run], defaults to 5, 50, 95
+    #[serde(default = "default_percentiles")]
+    pub percentiles: Vec<f64>,
+}
+
+impl MonteCarlo {
+    /// Returns sampled parameter values, one `Vec` per run in the order of
+    /// [Self::parameters]
+    pub fn samples(&self) -> anyhow::Result<Vec<Vec<f64>>> {
+        let mut rng = StdRng::seed_from_u64(self.seed);
+        (0..self.n)
+            .map(|_| {
+                self.parameters
+                    .iter()
+                    .map(|p| p.distribution.sample(&mut rng))
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Returns one copy of `base` per run with sampled parameters set
+    pub fn systems<S: SerdeAPI>(&self, base: &S) -> anyhow::Result<Vec<S>> {
+        let paths: Vec<&str> = self.parameters.iter().map(|p| p.path.as_str()).collect();
+        with_path_values(base, &paths, &self.samples()?)
+    }
+
+    /// Runs all samples of `base` -- in parallel with the `rayon` feature --
+    /// and returns `time` of the first run and, for every other history column
+    /// but `i` and every percentile `p`, the trajectory of that percentile
+    /// across runs named like `p95(m1.temp)`
+    pub fn run<S: Simulate + SerdeAPI>(&self, base: &S) -> anyhow::Result<Columns> {
+        let histories: Vec<Columns> = walk_all(self.systems(base)?)?
+            .iter()
+            .map(|sys| sys.history_columns())
+            .collect();
+        let first = histories
+            .first()
+            .ok_or_else(|| anyhow!("Monte Carlo `n` must be positive"))?;
+        // percentiles are taken row by row, so all runs must line up
+        for (j, history) in histories.iter().enumerate().skip(1) {
+            if history.names() != first.names() {
+                return Err(anyhow!("Run {} has different columns than run 0", j));
+            }
+            if let Some(((name, v), (_, v0))) = history
+                .iter()
+                .zip(first.iter())
+                .find(|((_, v), (_, v0))| v.len() != v0.len())
+            {
+                return Err(anyhow!(
+                    "Run {} has {} values of `{}` but run 0 has {}",
+                    j,
+                    v.len(),
+                    name,
+                    v0.len()
+                ));
+            }
+        }
+        // values of every column of every run
+        let values: Vec<Vec<&Vec<f64>>> = histories
+            .iter()
+            .map(|history| history.iter().map(|(_name, v)| v).collect())
+            .collect();
+        let mut bands = Columns::new();
+        for (k, name) in first.names().iter().enumerate() {
+            match name.as_str() {
+                "i" => continue,
+                "time" => {
+                    bands.push(name.clone(), values[0][k].clone());
+                    continue;
+                }
+                _ => {}
+            }
+            // sorted values across runs for every row
+            let rows: Vec<Vec<f64>> = (0..values[0][k].len())
+                .map(|i| {
+                    let mut row: Vec<f64> = values.iter().map(|run| run[k][i]).collect();
+                    row.sort_by(f64::total_cmp);
+                    row
+                })
+                .collect();
+            for p in &self.percentiles {
+                bands.push(
+                    format!("p{}({})", p, name),
+                    rows.iter().map(|row| percentile(row, *p)).collect(),
+                );
+            }
+        }
+        Ok(bands)
+    }
+}
+
+/// Returns percentile `p` in \[0, 100\] of `sorted` values, linearly
+/// interpolating between closest ranks
+pub fn percentile(sorted: &[f64], p: f64) -> f64 {
+    if sorted.is_empty() {
+        return f64::NAN;
+    }
+    let rank = (p / 100.0).clamp(0.0, 1.0) * (sorted.len() - 1) as f64;
+    let (lower, upper) = (rank.floor() as usize, rank.ceil() as usize);
+    sorted[lower] + (rank - lower as f64) * (sorted[upper] - sorted[lower])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_percentile() {
+        assert_eq!(percentile(&[1.0, 2.0, 3.0, 4.0], 50.0), 2.5);
+        assert_eq!(percentile(&[1.0, 2.0, 3.0, 4.0], 100.0), 4.0);
+        assert_eq!(percentile(&[1.0, 2.0, 3.0, 4.0], -5.0), 1.0);
+        assert!(percentile(&[], 50.0).is_nan());
+    }
+}
@@ -0,0 +1,615 @@
+//! Networks of components whose topology is defined at runtime, e.g. from
+//! YAML or JSON, rather than by a struct annotated with `#[solver]`
+
+use crate::components::*;
+use crate::ensemble::Simulate;
+use crate::imports::*;
+use crate::solver::*;
+use crate::time_series::HasBreakpoints;
+use crate::{SystemState, SystemStateHistoryVec};
+use std::fmt::Debug;
+
+/// Node of a [Network], i.e. a component with a potential state, e.g. a
+/// [ThermalMass]
+pub trait Node: HasState + Debug + Send + Sync {
+    /// Returns copy of node as [NodeTypes], through which boxed nodes are
+    /// cloned, compared, and (de)serialized
+    fn to_types(&self) -> NodeTypes;
+    /// Returns boxed copy of node with empty history
+    fn bare_boxed(&self) -> Box<dyn Node>;
+    /// Pushes current state to history
+    fn save_state(&mut self);
+    /// Returns history flattened into named columns
+    fn history_columns(&self) -> Columns;
+}
+
+/// Flow component connecting two nodes of a [Network], e.g. a [Conductance]
+/// or, with a flow that is a state in its own right, an [Inductor]
+pub trait Link: Flow + Debug + Send + Sync {
+    /// Returns copy of link as [LinkTypes], through which boxed links are
+    /// cloned, compared, and (de)serialized
+    fn to_types(&self) -> LinkTypes;
+    /// Returns boxed copy of link with empty history
+    fn bare_boxed(&self) -> Box<dyn Link>;
+    /// Pushes current state to history
+    fn save_state(&mut self);
+    /// Returns history flattened into named columns
+    fn history_columns(&self) -> Columns;
+    /// Returns link as [HasFlowState], if its flow is a state
+    fn as_flow_state(&self) -> Option<&dyn HasFlowState> {
+        None
+    }
+    /// Returns link as mut [HasFlowState], if its flow is a state
+    fn as_flow_state_mut(&mut self) -> Option<&mut dyn HasFlowState> {
+        None
+    }
+}
+
+/// Generates enum `$types` with a variant for each of component types
+/// `$ty`, implements `$trait` for each of them, and implements `Clone`,
+/// comparison, and serde traits for `Box<dyn $trait>` by way of `$types`,
+/// so that e.g. a node is written in YAML as `!ThermalMass {c: 1.0, ...}`.
+/// Types followed by `{flow_state}` are returned by `as_flow_state`.
+macro_rules! network_component_types {
+    (@flow_state) => {
+        fn as_flow_state(&self) -> Option<&dyn HasFlowState> {
+            Some(self)
+        }
+        fn as_flow_state_mut(&mut self) -> Option<&mut dyn HasFlowState> {
+            Some(self)
+        }
+    };
+    (
+        $(#[$meta: meta])*
+        $trait: ident,
+        $types: ident,
+        [$($ty: ident $({$flag: ident})?),+ $(,)?]
+    ) => {
+        $(#[$meta])*
+        #[common_derives]
+        pub enum $types {
+            $($ty($ty)),+
+        }
+
+        impl From<$types> for Box<dyn $trait> {
+            fn from(x: $types) -> Self {
+                match x {
+                    $($types::$ty(x) => Box::new(x)),+
+                }
+            }
+        }
+
+        $(
+            impl $trait for $ty {
+                fn to_types(&self) -> $types {
+                    $types::$ty(self.clone())
+                }
+                fn bare_boxed(&self) -> Box<dyn $trait> {
+                    Box::new(self.bare_clone())
+                }
+                fn save_state(&mut self) {
+                    <$ty>::save_state(self)
+                }
+                fn history_columns(&self) -> Columns {
+                    <$ty>::history_columns(self)
+                }
+                $(network_component_types!(@$flag);)?
+            }
+        )+
+
+        impl Clone for Box<dyn $trait> {
+            fn clone(&self) -> Self {
+                self.to_types().into()
+            }
+        }
+
+        impl PartialEq for dyn $trait {
+            fn eq(&self, other: &Self) -> bool {
+                self.to_types() == other.to_types()
+            }
+        }
+
+        impl PartialOrd for dyn $trait {
+            fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+                self.to_types().partial_cmp(&other.to_types())
+            }
+        }
+
+        impl Serialize for Box<dyn $trait> {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                self.to_types().serialize(serializer)
+            }
+        }
+
+        impl<'de> Deserialize<'de> for Box<dyn $trait> {
+            fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                $types::deserialize(deserializer).map(Into::into)
+            }
+        }
+    };
+}
+
+network_component_types!(
+    /// Component types that can be used as a [Node]
+    Node,
+    NodeTypes,
+    [
+        ThermalMass,
+        ThermalReservoir,
+        VariableThermalMass,
+        Capacitor,
+        VoltageSource,
+        Tank,
+        PressureReservoir,
+        Mass,
+        FixedGround,
+        Inertia,
+    ]
+);
+
+network_component_types!(
+    /// Component types that can be used as a [Link]
+    Link,
+    LinkTypes,
+    [
+        Conductance,
+        RadiativeConductance,
+        ConvectiveConductance,
+        Resistor,
+        Pipe,
+        Pump,
+        Damper,
+        Inductor {flow_state},
+        Spring {flow_state},
+        TorsionSpring {flow_state},
+    ]
+);
+
+/// Connection of [Network] node `from` to node `to`, with positive flow of
+/// `conductance` going from the former to the latter
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Edge {
+    /// index of first node
+    pub from: usize,
+    /// index of second node
+    pub to: usize,
+    pub conductance: Box<dyn Link>,
+}
+
+// derived comparisons try to move out of `Box<dyn Link>` fields
+impl PartialEq for Edge {
+    fn eq(&self, other: &Self) -> bool {
+        (self.from, self.to, &self.conductance) == (other.from, other.to, &other.conductance)
+    }
+}
+
+impl PartialOrd for Edge {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        (self.from, self.to, &self.conductance).partial_cmp(&(
+            other.from,
+            other.to,
+            &other.conductance,
+        ))
+    }
+}
+
+/// [FlowSource] injecting flow into [Network] node `node`, e.g. a heater
+#[derive(Debug, Clone, PartialEq, PartialOrd, Serialize, Deserialize)]
+pub struct Injection {
+    /// index of node
+    pub node: usize,
+    pub source: FlowSource,
+}
+
+/// System of `nodes` connected by `edges`, defined at runtime rather than by
+/// a struct annotated with `#[solver]`, e.g.
+/// ```yaml
+/// solver_type: !RK4Fixed {dt: 0.01}
+/// nodes:
+/// - !ThermalMass {c: 1.0}
+/// - !ThermalMass {c: 2.0, state: {temp: 10.0, dtemp: 0.0}}
+/// edges:
+/// - from: 0
+///   to: 1
+///   conductance: !Conductance {h: 5.0}
+/// t_report: [0.0, 0.5, 1.0]
+/// ```
+/// in which omitted states are zero and omitted histories are empty.
+/// Flows of links like [Inductor] are states that follow those of the
+/// nodes, and `sources` optionally inject flow into nodes, e.g.
+/// ```yaml
+/// sources:
+/// - node: 0
+///   source:
+///     signal: !Step {time: 0.5, before: 0.0, after: 1.0}
+/// ```
+/// and steps land on jumps in their signals.  Closures of
+/// [FlowSource::function] cannot be deserialized and must be reattached.
+/// Networks are checked with [Network::validate] when deserialized.
+/// States and histories are named like those of a `#[solver]` system with
+/// vec fields `nodes`, `edges`, and `sources`, e.g. `nodes.0`, `edges.0.q`,
+/// and `sources.0.flow`.
+#[pyo3_api(
+    #[getter]
+    fn get_solver_type(&self) -> String {
+        self.solver_type.to_json()
+    }
+
+    #[getter]
+    fn get_n_nodes(&self) -> usize {
+        self.nodes.len()
+    }
+
+    #[getter]
+    fn get_n_edges(&self) -> usize {
+        self.edges.len()
+    }
+
+    #[getter]
+    fn get_n_sources(&self) -> usize {
+        self.sources.len()
+    }
+
+    #[pyo3(name = "walk")]
+    fn walk_py(&mut self) -> PyResult<()> {
+        self.validate()?;
+        self.walk();
+        Ok(())
+    }
+
+    #[pyo3(name = "state_names")]
+    fn state_names_py(&self) -> Vec<String> {
+        self.state_names()
+    }
+
+    /// Returns dict of history flattened into named columns,
+    /// e.g. `time`, `nodes.0.temp`, `edges.0.q`
+    #[pyo3(name = "history_columns")]
+    fn history_columns_py<'py>(&self, py: Python<'py>) -> PyResult<&'py PyDict> {
+        let dict = PyDict::new(py);
+        for (name, values) in self.history_columns().iter() {
+            dict.set_item(name, values.clone())?;
+        }
+        Ok(dict)
+    }
+
+    /// Writes history flattened into named columns to `filename`.
+    /// Currently `.csv` and `.json` suffixes are supported.
+    fn history_to_file(&self, filename: &str) -> PyResult<()> {
+        Ok(self.history_columns().to_file(filename)?)
+    }
+)]
+#[common_derives]
+#[derive(Default)]
+#[serde(try_from = "NetworkUnchecked")]
+pub struct Network {
+    #[skip_get]
+    pub solver_type: SolverTypes,
+    #[skip_get]
+    pub nodes: Vec<Box<dyn Node>>,
+    #[skip_get]
+    pub edges: Vec<Edge>,
+    #[serde(default)]
+    #[skip_get]
+    pub sources: Vec<Injection>,
+    pub t_report: Vec<f64>,
+    #[serde(default)]
+    pub state: SystemState,
+    #[serde(default)]
+    pub history: SystemStateHistoryVec,
+}
+
+/// [Network] as deserialized, before it is validated
+#[derive(Deserialize)]
+struct NetworkUnchecked {
+    solver_type: SolverTypes,
+    nodes: Vec<Box<dyn Node>>,
+    edges: Vec<Edge>,
+    #[serde(default)]
+    sources: Vec<Injection>,
+    t_report: Vec<f64>,
+    #[serde(default)]
+    state: SystemState,
+    #[serde(default)]
+    history: SystemStateHistoryVec,
+}
+
+impl TryFrom<NetworkUnchecked> for Network {
+    type Error = anyhow::Error;
+
+    fn try_from(network: NetworkUnchecked) -> anyhow::Result<Self> {
+        let network = Self {
+            solver_type: network.solver_type,
+            nodes: network.nodes,
+            edges: network.edges,
+            sources: network.sources,
+            t_report: network.t_report,
+            state: network.state,
+            history: network.history,
+        };
+        network.validate()?;
+        Ok(network)
+    }
+}
+
+impl Network {
+    /// Returns error if an edge or source refers to a missing node, an edge
+    /// connects a node to itself, or `t_report` is empty
+    pub fn validate(&self) -> anyhow::Result<()> {
+        for (i, edge) in self.edges.iter().enumerate() {
+            for node in [edge.from, edge.to] {
+                if node >= self.nodes.len() {
+                    return Err(anyhow!(
+                        "edge {} refers to node {}, but network has {} nodes",
+                        i,
+                        node,
+                        self.nodes.len()
+                    ));
+                }
+            }
+            if edge.from == edge.to {
+                return Err(anyhow!("edge {} connects node {} to itself", i, edge.from));
+            }
+        }
+        for (i, source) in self.sources.iter().enumerate() {
+            if source.node >= self.nodes.len() {
+                return Err(anyhow!(
+                    "source {} refers to node {}, but network has {} nodes",
+                    i,
+                    source.node,
+                    self.nodes.len()
+                ));
+            }
+        }
+        if self.t_report.is_empty() {
+            return Err(anyhow!("`t_report` is empty"));
+        }
+        Ok(())
+    }
+
+    /// iterates through time until last value of `t_report`
+    ///
+    /// # Panics
+    /// If [Self::validate] fails, which can only happen for a network that
+    /// was built or modified in code rather than deserialized
+    pub fn walk(&mut self) {
+        self.validate().expect("Invalid network");
+        while &self.state.time < self.t_report.last().unwrap() {
+            self.solve_step();
+            self.state.i += 1;
+            self.save_state();
+        }
+    }
+
+    /// Pushes current state of network, nodes, edges, and sources to their
+    /// histories
+    pub fn save_state(&mut self) {
+        self.history.push(self.state);
+        self.nodes.iter_mut().for_each(|node| node.save_state());
+        self.edges
+            .iter_mut()
+            .for_each(|edge| edge.conductance.save_state());
+        self.sources
+            .iter_mut()
+            .for_each(|source| source.source.save_state());
+    }
+
+    /// Returns history of network, nodes, edges, and sources flattened into
+    /// columns, e.g. `time`, `nodes.0.temp`, `edges.0.q`, `sources.0.flow`
+    pub fn history_columns(&self) -> Columns {
+        let mut columns = self.history.columns();
+        for (i, node) in self.nodes.iter().enumerate() {
+            columns.extend_prefixed(&format!("nodes.{}", i), node.history_columns());
+        }
+        for (i, edge) in self.edges.iter().enumerate() {
+            columns.extend_prefixed(&format!("edges.{}", i), edge.conductance.history_columns());
+        }
+        for (i, source) in self.sources.iter().enumerate() {
+            columns.extend_prefixed(&format!("sources.{}", i), source.source.history_columns());
+        }
+        columns
+    }
+
+    /// Returns links of edges whose flows are states
+    fn flow_states(&self) -> impl Iterator<Item = &dyn HasFlowState> + '_ {
+        self.edges
+            .iter()
+            .filter_map(|edge| edge.conductance.as_flow_state())
+    }
+
+    /// Returns mut links of edges whose flows are states
+    fn flow_states_mut(&mut self) -> impl Iterator<Item = &mut dyn HasFlowState> + '_ {
+        self.edges
+            .iter_mut()
+            .filter_map(|edge| edge.conductance.as_flow_state_mut())
+    }
+}
+
+impl BareClone for Network {
+    fn bare_clone(&self) -> Self {
+        Self {
+            solver_type: self.solver_type.clone(),
+            nodes: self.nodes.iter().map(|node| node.bare_boxed()).collect(),
+            edges: self
+                .edges
+                .iter()
+                .map(|edge| Edge {
+                    from: edge.from,
+                    to: edge.to,
+                    conductance: edge.conductance.bare_boxed(),
+                })
+                .collect(),
+            sources: self
+                .sources
+                .iter()
+                .map(|source| Injection {
+                    node: source.node,
+                    source: source.source.bare_clone(),
+                })
+                .collect(),
+            t_report: self.t_report.clone(),
+            state: self.state,
+            history: Default::default(),
+        }
+    }
+}
+
+/// States of the nodes followed by those of links like [Inductor]
+impl HasStates for Network {
+    fn states(&self) -> Vec<f64> {
+        let mut states: Vec<f64> = self.nodes.iter().map(|node| node.state()).collect();
+        states.extend(self.flow_states().map(|link| link.flow()));
+        states
+    }
+    fn set_states(&mut self, val: Vec<f64>) {
+        let n = self.nodes.len();
+        assert_eq!(val.len(), n + self.flow_states().count());
+        for (node, val) in self.nodes.iter_mut().zip(&val) {
+            node.set_state(*val);
+        }
+        for (link, val) in self.flow_states_mut().zip(&val[n..]) {
+            link.set_flow_state(*val);
+        }
+    }
+    fn step_states_by_dt(&mut self, dt: &f64) {
+        self.nodes
+            .iter_mut()
+            .for_each(|node| node.step_state_by_dt(dt));
+        self.flow_states_mut()
+            .for_each(|link| link.step_flow_state_by_dt(dt));
+        self.step_time(dt);
+    }
+    fn step_states(&mut self, val: Vec<f64>) {
+        let n = self.nodes.len();
+        assert_eq!(val.len(), n + self.flow_states().count());
+        for (node, val) in self.nodes.iter_mut().zip(&val) {
+            node.step_state(*val);
+        }
+        for (link, val) in self.flow_states_mut().zip(&val[n..]) {
+            link.step_flow_state(*val);
+        }
+    }
+    fn derivs(&self) -> Vec<f64> {
+        let mut derivs: Vec<f64> = self.nodes.iter().map(|node| node.deriv()).collect();
+        derivs.extend(self.flow_states().map(|link| link.flow_deriv()));
+        derivs
+    }
+    fn set_derivs(&mut self, val: &[f64]) {
+        let n = self.nodes.len();
+        assert_eq!(val.len(), n + self.flow_states().count());
+        for (node, val) in self.nodes.iter_mut().zip(val) {
+            node.set_deriv(*val);
+        }
+        for (link, val) in self.flow_states_mut().zip(&val[n..]) {
+            link.set_flow_deriv(*val);
+        }
+    }
+    fn step_derivs(&mut self, val: Vec<f64>) {
+        let n = self.nodes.len();
+        assert_eq!(val.len(), n + self.flow_states().count());
+        for (node, val) in self.nodes.iter_mut().zip(&val) {
+            node.step_deriv(*val);
+        }
+        for (link, val) in self.flow_states_mut().zip(&val[n..]) {
+            link.step_flow_deriv(*val);
+        }
+    }
+    fn storages(&self) -> Vec<f64> {
+        let mut storages: Vec<f64> = self.nodes.iter().map(|node| node.storage()).collect();
+        storages.extend(self.flow_states().map(|link| link.inertance()));
+        storages
+    }
+    fn state_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = (0..self.nodes.len())
+            .map(|i| format!("nodes.{}", i))
+            .collect();
+        names.extend(
+            self.edges
+                .iter()
+                .enumerate()
+                .filter(|(_, edge)| edge.conductance.as_flow_state().is_some())
+                .map(|(i, _)| format!("edges.{}", i)),
+        );
+        names
+    }
+}
+
+impl SolverBase for Network {
+    fn reset_derivs(&mut self) {
+        self.nodes.iter_mut().for_each(|node| node.set_deriv(0.0));
+        self.flow_states_mut()
+            .for_each(|link| link.set_flow_deriv(0.0));
+    }
+    /// Sets flows of all edges and sources and then steps derivatives of the
+    /// nodes they connect, as `connect_states!` and `update_derivs!` do
+    fn update_derivs(&mut self) {
+        self.reset_derivs();
+        for edge in self.edges.iter_mut() {
+            edge.conductance
+                .set_flow(&*self.nodes[edge.from], &*self.nodes[edge.to]);
+        }
+        for edge in self.edges.iter() {
+            let flow = edge.conductance.flow();
+            let storage = self.nodes[edge.from].storage();
+            self.nodes[edge.from].step_deriv(-flow / storage);
+            let storage = self.nodes[edge.to].storage();
+            self.nodes[edge.to].step_deriv(flow / storage);
+        }
+        for source in self.sources.iter_mut() {
+            source
+                .source
+                .set_source_flow(&*self.nodes[source.node], self.state.time);
+        }
+        for source in self.sources.iter() {
+            let flow = source.source.flow();
+            let storage = self.nodes[source.node].storage();
+            self.nodes[source.node].step_deriv(flow / storage);
+        }
+    }
+    fn step_time(&mut self, dt: &f64) {
+        self.state.time += dt;
+    }
+    fn set_time(&mut self, t: f64) {
+        self.state.time = t;
+    }
+    fn sc(&self) -> Option<&AdaptiveSolverConfig> {
+        match &self.solver_type {
+            SolverTypes::RK45CashKarp(sc) => Some(sc),
+            SolverTypes::RK23BogackiShampine(sc) => Some(sc),
+            _ => None,
+        }
+    }
+    fn sc_mut(&mut self) -> Option<&mut AdaptiveSolverConfig> {
+        match &mut self.solver_type {
+            SolverTypes::RK45CashKarp(sc) => Some(sc),
+            SolverTypes::RK23BogackiShampine(sc) => Some(sc),
+            _ => None,
+        }
+    }
+    fn state(&self) -> &SystemState {
+        &self.state
+    }
+    fn solver_type(&self) -> &SolverTypes {
+        &self.solver_type
+    }
+    fn t_report(&self) -> &[f64] {
+        &self.t_report
+    }
+    fn next_breakpoint(&self) -> Option<f64> {
+        self.sources
+            .iter()
+            .filter_map(|source| source.source.next_breakpoint(self.state.time))
+            .reduce(f64::min)
+    }
+}
+
+impl SolverVariantMethods for Network {}
+
+impl Simulate for Network {
+    fn walk(&mut self) {
+        Network::walk(self)
+    }
+    fn history_columns(&self) -> Columns {
+        Network::history_columns(self)
+    }
+}
@@ -0,0 +1,117 @@
+#[cfg(feature = "pyo3")]
+use crate::imports::*;
+use crate::solver::SolverState;
+use std::sync::{Arc, Mutex};
+
+/// Snapshot of the system passed to each [Observer] after every internal
+/// solver step, accepted or rejected.
+pub struct StepInfo<'a> {
+    /// system time -- after the step if accepted, before the attempt if rejected
+    pub time: f64,
+    /// values of states
+    pub states: &'a [f64],
+    /// time derivatives of states
+    pub derivs: &'a [f64],
+    /// time step size used (accepted) or attempted (rejected)
+    pub dt: f64,
+    /// `true` if the step was accepted, `false` if an adaptive solver rejected it
+    pub accepted: bool,
+    /// adaptive solver state, if applicable
+    pub solver_state: Option<&'a SolverState>,
+}
+
+/// Receives a [StepInfo] after every internal solver step, regardless of
+/// whether the step lands on a `t_report` time.
+pub trait Observer: Send {
+    fn on_step(&mut self, info: &StepInfo);
+}
+
+impl<F> Observer for F
+where
+    F: FnMut(&StepInfo) + Send,
+{
+    fn on_step(&mut self, info: &StepInfo) {
+        self(info)
+    }
+}
+
+/// Collection of [Observer]s attached to a system.
+/// Systems opt in with a field of this type marked `#[observers]`, which
+/// should also be marked `#[serde(skip)]` because observers cannot be serialized.
+/// Clones share the same observers, and observers never affect equality or
+/// ordering of the system.
+#[derive(Clone, Default)]
+pub struct Observers(Vec<Arc<Mutex<dyn Observer>>>);
+
+impl Observers {
+    /// Attaches `observer`
+    pub fn attach(&mut self, observer: impl Observer + 'static) {
+        self.0.push(Arc::new(Mutex::new(observer)));
+    }
+
+    /// Passes `info` to every attached observer
+    pub fn notify(&self, info: &StepInfo) {
+        for observer in &self.0 {
+            observer.lock().unwrap().on_step(info);
+        }
+    }
+
+    /// Detaches all observers
+    pub fn clear(&mut self) {
+        self.0.clear();
+    }
+
+    /// Returns number of attached observers
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns true if no observers are attached
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl std::fmt::Debug for Observers {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Observers({})", self.len())
+    }
+}
+
+impl PartialEq for Observers {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl PartialOrd for Observers {
+    fn partial_cmp(&self, _other: &Self) -> Option<std::cmp::Ordering> {
+        Some(std::cmp::Ordering::Equal)
+    }
+}
+
+#[cfg(feature = "pyo3")]
+/// [Observer] that calls a python callable with arguments
+/// `(time, states, derivs, dt, accepted, solver_state)`
+pub struct PyObserver {
+    pub callback: PyObject,
+}
+
+#[cfg(feature = "pyo3")]
+impl Observer for PyObserver {
+    fn on_step(&mut self, info: &StepInfo) {
+        Python::with_gil(|py| {
+            let args = (
+                info.time,
+                info.states.to_vec(),
+                info.derivs.to_vec(),
+                info.dt,
+                info.accepted,
+                info.solver_state.cloned(),
+            );
+            if let Err(err) = self.callback.call1(py, args) {
+                err.print(py);
+            }
+        });
+    }
+}
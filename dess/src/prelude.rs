@@ -1,9 +1,23 @@
+pub use crate::columns::*;
 pub use crate::connect_states;
+#[cfg(feature = "doe")]
+pub use crate::doe::*;
+pub use crate::ensemble::*;
+#[cfg(feature = "monte_carlo")]
+pub use crate::monte_carlo::*;
+pub use crate::network::*;
+pub use crate::observer::*;
 pub use crate::print_to_py;
+pub use crate::retention::*;
+pub use crate::signal::*;
+pub use crate::sink::*;
+pub use crate::snapshot::*;
 #[cfg(feature = "pyo3")]
 pub use crate::pyo3_imports::*;
 pub use crate::solver::*;
+pub use crate::subsystem::*;
 pub use crate::time_it;
+pub use crate::time_series::*;
 pub use crate::traits_and_macros::*;
 pub use crate::update_derivs;
 pub use crate::utilities::*;
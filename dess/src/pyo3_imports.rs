@@ -1,6 +1,6 @@
 pub use pyo3::exceptions::{
-    PyAttributeError, PyFileNotFoundError, PyNotImplementedError, PyValueError,
+    PyAttributeError, PyFileNotFoundError, PyIndexError, PyNotImplementedError, PyValueError,
 };
 pub use pyo3::ffi::{getter, setter};
 pub use pyo3::prelude::*;
-pub use pyo3::types::{PyBytes, PyDict, PyType};
+pub use pyo3::types::{PyBytes, PyDict, PyList, PySlice, PyType};
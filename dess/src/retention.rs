@@ -0,0 +1,76 @@
+use crate::imports::*;
+
+/// Policy for how much history is kept in memory.  Systems opt in with a
+/// field of this type marked `#[retention]`, which is then honored by
+/// `save_state` and, when `AdaptiveSolverConfig::save` is true, by the
+/// adaptive solver's `SolverStateHistoryVec`.
+#[common_derives]
+#[derive(Copy, Default)]
+pub enum Retention {
+    /// keep every saved state
+    #[default]
+    All,
+    /// keep every `n`th saved state, starting with the first
+    EveryNth { n: usize },
+    /// keep only the last `n` saved states, like a ring buffer.  Histories
+    /// are trimmed in batches, so up to `2 * n` states are held during a run
+    /// and exactly `n` once it finishes.
+    LastN { n: usize },
+    /// keep no saved states
+    Discard,
+}
+
+impl Retention {
+    /// Returns true if the saved state with zero-based `index` should be kept
+    // `usize::is_multiple_of` needs Rust 1.87
+    #[allow(clippy::manual_is_multiple_of)]
+    pub fn keep(&self, index: usize) -> bool {
+        match self {
+            Retention::All | Retention::LastN { .. } => true,
+            Retention::EveryNth { n } => index % (*n).max(1) == 0,
+            Retention::Discard => false,
+        }
+    }
+
+    /// Returns max number of saved states to keep, if limited
+    pub fn max_len(&self) -> Option<usize> {
+        match self {
+            Retention::LastN { n } => Some(*n),
+            Retention::Discard => Some(0),
+            _ => None,
+        }
+    }
+
+    /// Returns [Self::max_len] if history of `len` saved states is due to be
+    /// trimmed to it.  Trimming only once `len` exceeds twice the limit keeps
+    /// its cost per saved state constant.
+    pub fn trim_to(&self, len: usize) -> Option<usize> {
+        self.max_len().filter(|n| len > 2 * n)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_retention() {
+        let every_3rd = Retention::EveryNth { n: 3 };
+        assert_eq!(
+            (0..7).filter(|i| every_3rd.keep(*i)).collect::<Vec<_>>(),
+            [0, 3, 6]
+        );
+        assert_eq!(every_3rd.max_len(), None);
+        // `n` of 0 keeps every state rather than dividing by zero
+        assert!(Retention::EveryNth { n: 0 }.keep(1));
+
+        let last_4 = Retention::LastN { n: 4 };
+        assert!(last_4.keep(100));
+        assert_eq!(last_4.trim_to(8), None);
+        assert_eq!(last_4.trim_to(9), Some(4));
+
+        assert!(!Retention::Discard.keep(0));
+        assert_eq!(Retention::Discard.trim_to(1), Some(0));
+        assert_eq!(Retention::All.trim_to(usize::MAX), None);
+    }
+}
@@ -0,0 +1,421 @@
+use crate::imports::*;
+use crate::time_series::*;
+use std::f64::consts::PI;
+
+/// Prescribed value as a function of time, e.g. the temperature of a
+/// reservoir or the output of a source.  Breakpoints are the times at which
+/// the value or its derivative is discontinuous.
+pub trait Signal: HasBreakpoints {
+    /// Returns value at time `t`
+    fn value(&self, t: f64) -> f64;
+    /// Returns error if parameters would make [Self::value] or
+    /// [HasBreakpoints::next_breakpoint] meaningless, e.g. a zero period.
+    /// Called when [SignalTypes] are deserialized.
+    fn validate(&self) -> anyhow::Result<()> {
+        Ok(())
+    }
+}
+
+/// Deserializes signal and checks it with [Signal::validate]
+fn deserialize_valid<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: Signal + Deserialize<'de>,
+{
+    let signal = T::deserialize(deserializer)?;
+    signal.validate().map_err(serde::de::Error::custom)?;
+    Ok(signal)
+}
+
+/// Returns first of `times` after `t`
+fn first_after(t: f64, times: impl IntoIterator<Item = f64>) -> Option<f64> {
+    times.into_iter().find(|&t_b| !at_or_past(t, t_b))
+}
+
+/// Returns earliest of `breakpoints`
+fn earliest(breakpoints: impl IntoIterator<Item = Option<f64>>) -> Option<f64> {
+    breakpoints.into_iter().flatten().reduce(f64::min)
+}
+
+/// Constant value
+#[derive(Default, Copy)]
+#[common_derives]
+pub struct Constant(pub f64);
+
+impl Signal for Constant {
+    fn value(&self, _t: f64) -> f64 {
+        self.0
+    }
+}
+
+impl HasBreakpoints for Constant {
+    fn next_breakpoint(&self, _t: f64) -> Option<f64> {
+        None
+    }
+}
+
+/// Jumps from `before` to `after` at `time`
+#[derive(Default, Copy)]
+#[common_derives]
+pub struct Step {
+    pub time: f64,
+    #[serde(default)]
+    pub before: f64,
+    pub after: f64,
+}
+
+impl Signal for Step {
+    fn value(&self, t: f64) -> f64 {
+        if at_or_past(t, self.time) {
+            self.after
+        } else {
+            self.before
+        }
+    }
+}
+
+impl HasBreakpoints for Step {
+    fn next_breakpoint(&self, t: f64) -> Option<f64> {
+        first_after(t, [self.time])
+    }
+}
+
+/// Goes linearly from `start_value` at `start_time` to `end_value` at
+/// `end_time` and holds those values outside of that interval
+#[derive(Default, Copy)]
+#[common_derives]
+pub struct Ramp {
+    pub start_time: f64,
+    pub end_time: f64,
+    #[serde(default)]
+    pub start_value: f64,
+    pub end_value: f64,
+}
+
+impl Signal for Ramp {
+    fn value(&self, t: f64) -> f64 {
+        interpolate(
+            &[self.start_time, self.end_time],
+            &[self.start_value, self.end_value],
+            Interpolation::Linear,
+            t,
+        )
+    }
+
+    fn validate(&self) -> anyhow::Result<()> {
+        if self.start_time.is_nan() || self.end_time.is_nan() || self.start_time > self.end_time {
+            return Err(anyhow!(
+                "Ramp `start_time` {} must not exceed `end_time` {}",
+                self.start_time,
+                self.end_time
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl HasBreakpoints for Ramp {
+    fn next_breakpoint(&self, t: f64) -> Option<f64> {
+        first_after(t, [self.start_time, self.end_time])
+    }
+}
+
+/// Rectangular pulses of `high` lasting `width` every `period`, starting at
+/// `delay`, and `low` otherwise
+#[derive(Default, Copy)]
+#[common_derives]
+pub struct PulseTrain {
+    pub period: f64,
+    pub width: f64,
+    #[serde(default)]
+    pub delay: f64,
+    #[serde(default)]
+    pub low: f64,
+    pub high: f64,
+}
+
+impl PulseTrain {
+    /// Returns start of the period containing `t`, which is at or after `delay`
+    fn period_start(&self, t: f64) -> f64 {
+        let k = ((t - self.delay) / self.period).floor();
+        let start = self.delay + k * self.period;
+        // guard against rounding just short of the next period
+        if at_or_past(t, start + self.period) {
+            start + self.period
+        } else {
+            start
+        }
+    }
+}
+
+impl Signal for PulseTrain {
+    fn value(&self, t: f64) -> f64 {
+        if !at_or_past(t, self.delay) {
+            return self.low;
+        }
+        if at_or_past(t, self.period_start(t) + self.width) {
+            self.low
+        } else {
+            self.high
+        }
+    }
+
+    fn validate(&self) -> anyhow::Result<()> {
+        if self.period.is_nan() || self.period <= 0.0 {
+            return Err(anyhow!(
+                "PulseTrain `period` must be positive, not {}",
+                self.period
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl HasBreakpoints for PulseTrain {
+    fn next_breakpoint(&self, t: f64) -> Option<f64> {
+        if !at_or_past(t, self.delay) {
+            return Some(self.delay);
+        }
+        let start = self.period_start(t);
+        first_after(t, [start + self.width, start + self.period])
+    }
+}
+
+/// `amplitude * sin(2 pi frequency t + phase)`
+#[derive(Default, Copy)]
+#[common_derives]
+pub struct Sine {
+    pub amplitude: f64,
+    /// frequency \[Hz\]
+    pub frequency: f64,
+    /// phase \[rad\]
+    #[serde(default)]
+    pub phase: f64,
+}
+
+impl Signal for Sine {
+    fn value(&self, t: f64) -> f64 {
+        self.amplitude * f64::sin(2. * PI * self.frequency * t + self.phase)
+    }
+}
+
+impl HasBreakpoints for Sine {
+    fn next_breakpoint(&self, _t: f64) -> Option<f64> {
+        None
+    }
+}
+
+/// Sine whose frequency increases linearly from `start_frequency` at `t = 0`
+/// to `end_frequency` at `duration` and keeps increasing at the same rate
+#[derive(Default, Copy)]
+#[common_derives]
+pub struct Chirp {
+    pub amplitude: f64,
+    /// frequency at `t = 0` \[Hz\]
+    pub start_frequency: f64,
+    /// frequency at `duration` \[Hz\]
+    pub end_frequency: f64,
+    /// \[s\]
+    pub duration: f64,
+    /// phase \[rad\]
+    #[serde(default)]
+    pub phase: f64,
+}
+
+impl Signal for Chirp {
+    fn value(&self, t: f64) -> f64 {
+        let rate = (self.end_frequency - self.start_frequency) / self.duration;
+        self.amplitude
+            * f64::sin(2. * PI * (self.start_frequency * t + rate / 2. * t * t) + self.phase)
+    }
+
+    fn validate(&self) -> anyhow::Result<()> {
+        if self.duration == 0.0 {
+            return Err(anyhow!("Chirp `duration` must not be zero"));
+        }
+        Ok(())
+    }
+}
+
+impl HasBreakpoints for Chirp {
+    fn next_breakpoint(&self, _t: f64) -> Option<f64> {
+        None
+    }
+}
+
+/// `amplitude * exp(-t / time_constant)`
+#[derive(Default, Copy)]
+#[common_derives]
+pub struct Exponential {
+    pub amplitude: f64,
+    /// \[s\], negative for growth
+    pub time_constant: f64,
+}
+
+impl Signal for Exponential {
+    fn value(&self, t: f64) -> f64 {
+        self.amplitude * f64::exp(-t / self.time_constant)
+    }
+
+    fn validate(&self) -> anyhow::Result<()> {
+        if self.time_constant == 0.0 {
+            return Err(anyhow!("Exponential `time_constant` must not be zero"));
+        }
+        Ok(())
+    }
+}
+
+impl HasBreakpoints for Exponential {
+    fn next_breakpoint(&self, _t: f64) -> Option<f64> {
+        None
+    }
+}
+
+/// Straight lines between points, held constant outside of `time`
+#[derive(Default)]
+#[common_derives]
+pub struct PiecewiseLinear {
+    /// strictly increasing times \[s\]
+    pub time: Vec<f64>,
+    pub value: Vec<f64>,
+}
+
+impl Signal for PiecewiseLinear {
+    fn value(&self, t: f64) -> f64 {
+        interpolate(&self.time, &self.value, Interpolation::Linear, t)
+    }
+
+    fn validate(&self) -> anyhow::Result<()> {
+        validate_points("PiecewiseLinear", &self.time, &self.value)
+    }
+}
+
+impl HasBreakpoints for PiecewiseLinear {
+    fn next_breakpoint(&self, t: f64) -> Option<f64> {
+        first_after(t, self.time.iter().copied())
+    }
+}
+
+impl Signal for TimeSeriesInput {
+    fn value(&self, t: f64) -> f64 {
+        self.value_at(t)
+    }
+
+    fn validate(&self) -> anyhow::Result<()> {
+        TimeSeriesInput::validate(self)
+    }
+}
+
+/// Sum of signals
+#[derive(Default)]
+#[common_derives]
+pub struct Sum(pub Vec<SignalTypes>);
+
+impl Signal for Sum {
+    fn value(&self, t: f64) -> f64 {
+        self.0.iter().map(|signal| signal.value(t)).sum()
+    }
+
+    fn validate(&self) -> anyhow::Result<()> {
+        self.0.iter().try_for_each(Signal::validate)
+    }
+}
+
+impl HasBreakpoints for Sum {
+    fn next_breakpoint(&self, t: f64) -> Option<f64> {
+        earliest(self.0.iter().map(|signal| signal.next_breakpoint(t)))
+    }
+}
+
+/// Product of signals
+#[derive(Default)]
+#[common_derives]
+pub struct Product(pub Vec<SignalTypes>);
+
+impl Signal for Product {
+    fn value(&self, t: f64) -> f64 {
+        self.0.iter().map(|signal| signal.value(t)).product()
+    }
+
+    fn validate(&self) -> anyhow::Result<()> {
+        self.0.iter().try_for_each(Signal::validate)
+    }
+}
+
+impl HasBreakpoints for Product {
+    fn next_breakpoint(&self, t: f64) -> Option<f64> {
+        earliest(self.0.iter().map(|signal| signal.next_breakpoint(t)))
+    }
+}
+
+/// Any of the provided signals, e.g. in YAML
+/// ```yaml
+/// !Sum
+///   - !Constant 10.0
+///   - !Product
+///     - !Chirp {amplitude: 3.0, start_frequency: 0.0, end_frequency: 10.0, duration: 1.0}
+///     - !Exponential {amplitude: 1.0, time_constant: 0.25}
+///   - !Step {time: 0.5, after: 2.0}
+/// ```
+/// Signals are checked with [Signal::validate] when deserialized.
+#[common_derives]
+pub enum SignalTypes {
+    Constant(Constant),
+    Step(Step),
+    #[serde(deserialize_with = "deserialize_valid")]
+    Ramp(Ramp),
+    #[serde(deserialize_with = "deserialize_valid")]
+    PulseTrain(PulseTrain),
+    Sine(Sine),
+    #[serde(deserialize_with = "deserialize_valid")]
+    Chirp(Chirp),
+    #[serde(deserialize_with = "deserialize_valid")]
+    Exponential(Exponential),
+    #[serde(deserialize_with = "deserialize_valid")]
+    PiecewiseLinear(PiecewiseLinear),
+    #[serde(deserialize_with = "deserialize_valid")]
+    TimeSeries(TimeSeriesInput),
+    Sum(Sum),
+    Product(Product),
+}
+
+impl Default for SignalTypes {
+    fn default() -> Self {
+        Self::Constant(Default::default())
+    }
+}
+
+/// Evaluates `$body` with `$signal` bound to whichever signal `$self` holds
+macro_rules! match_signal {
+    ($self:expr, $signal:ident => $body:expr) => {
+        match $self {
+            SignalTypes::Constant($signal) => $body,
+            SignalTypes::Step($signal) => $body,
+            SignalTypes::Ramp($signal) => $body,
+            SignalTypes::PulseTrain($signal) => $body,
+            SignalTypes::Sine($signal) => $body,
+            SignalTypes::Chirp($signal) => $body,
+            SignalTypes::Exponential($signal) => $body,
+            SignalTypes::PiecewiseLinear($signal) => $body,
+            SignalTypes::TimeSeries($signal) => $body,
+            SignalTypes::Sum($signal) => $body,
+            SignalTypes::Product($signal) => $body,
+        }
+    };
+}
+
+impl Signal for SignalTypes {
+    fn value(&self, t: f64) -> f64 {
+        match_signal!(self, signal => signal.value(t))
+    }
+
+    fn validate(&self) -> anyhow::Result<()> {
+        match_signal!(self, signal => signal.validate())
+    }
+}
+
+impl HasBreakpoints for SignalTypes {
+    fn next_breakpoint(&self, t: f64) -> Option<f64> {
+        match_signal!(self, signal => signal.next_breakpoint(t))
+    }
+}
@@ -0,0 +1,254 @@
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+/// Destination for rows of flattened system state streamed by `save_state`
+pub trait HistorySink: Send {
+    /// writes column names -- called once before the first row
+    fn write_header(&mut self, names: &[String]) -> anyhow::Result<()>;
+    /// writes one row of values in the same order as the header
+    fn write_row(&mut self, values: &[f64]) -> anyhow::Result<()>;
+    /// flushes any buffered rows
+    fn flush(&mut self) -> anyhow::Result<()> {
+        Ok(())
+    }
+}
+
+/// [HistorySink] that writes comma separated values with a header row
+pub struct CsvSink<W: Write + Send> {
+    writer: W,
+}
+
+impl<W: Write + Send> CsvSink<W> {
+    pub fn new(writer: W) -> Self {
+        Self { writer }
+    }
+}
+
+impl CsvSink<BufWriter<File>> {
+    /// Creates (or truncates) file at `path` and returns buffered sink for it
+    pub fn from_path<P: AsRef<Path>>(path: P) -> anyhow::Result<Self> {
+        Ok(Self::new(BufWriter::new(File::create(path)?)))
+    }
+}
+
+impl<W: Write + Send> HistorySink for CsvSink<W> {
+    fn write_header(&mut self, names: &[String]) -> anyhow::Result<()> {
+        writeln!(self.writer, "{}", names.join(","))?;
+        Ok(())
+    }
+    fn write_row(&mut self, values: &[f64]) -> anyhow::Result<()> {
+        let row: Vec<String> = values.iter().map(|v| v.to_string()).collect();
+        writeln!(self.writer, "{}", row.join(","))?;
+        Ok(())
+    }
+    fn flush(&mut self) -> anyhow::Result<()> {
+        self.writer.flush()?;
+        Ok(())
+    }
+}
+
+struct SinkInner {
+    sink: Box<dyn HistorySink>,
+    header_written: bool,
+    /// first error returned by `sink`, after which nothing more is written
+    error: Option<anyhow::Error>,
+}
+
+/// Optional [HistorySink] attached to a system.
+/// Systems opt in with a field of this type marked `#[sink]`, which should
+/// also be marked `#[serde(skip)]`.  When a sink is attached, `save_state` streams
+/// a row to it and, unless `keep_history` is false, also pushes to the
+/// in-memory history vecs.  Clones share the same sink, and the sink never
+/// affects equality or ordering of the system.
+///
+/// Writing never interrupts a run: the first error from the attached
+/// [HistorySink], e.g. a full disk, is kept, later rows are dropped, and the
+/// error is returned by [Self::flush] and [Self::detach].
+#[derive(Clone)]
+pub struct Sink {
+    inner: Option<Arc<Mutex<SinkInner>>>,
+    keep_history: bool,
+}
+
+impl Default for Sink {
+    fn default() -> Self {
+        Self {
+            inner: None,
+            keep_history: true,
+        }
+    }
+}
+
+impl Sink {
+    /// Attaches `sink`, replacing any previously attached sink.
+    /// If `keep_history` is false, in-memory history vecs are not populated.
+    pub fn attach(&mut self, sink: impl HistorySink + 'static, keep_history: bool) {
+        self.inner = Some(Arc::new(Mutex::new(SinkInner {
+            sink: Box::new(sink),
+            header_written: false,
+            error: None,
+        })));
+        self.keep_history = keep_history;
+    }
+
+    /// Flushes and detaches sink, if any, and resumes populating history vecs
+    pub fn detach(&mut self) -> anyhow::Result<()> {
+        self.flush()?;
+        self.inner = None;
+        self.keep_history = true;
+        Ok(())
+    }
+
+    /// Returns true if a sink is attached
+    pub fn is_attached(&self) -> bool {
+        self.inner.is_some()
+    }
+
+    /// Returns true if `self` and `other` are clones of the same attached sink
+    pub fn shares_with(&self, other: &Sink) -> bool {
+        match (&self.inner, &other.inner) {
+            (Some(a), Some(b)) => Arc::ptr_eq(a, b),
+            _ => false,
+        }
+    }
+
+    /// Returns true if in-memory history vecs should be populated
+    pub fn keep_history(&self) -> bool {
+        self.keep_history || self.inner.is_none()
+    }
+
+    /// Writes `values` to attached sink, preceded by header from `names` if
+    /// this is the first row.  Does nothing once the sink has failed, and
+    /// keeps the first error for [Self::flush].
+    pub fn write<F>(&self, names: F, values: &[f64])
+    where
+        F: FnOnce() -> Vec<String>,
+    {
+        if let Some(inner) = &self.inner {
+            let mut inner = inner.lock().unwrap();
+            if inner.error.is_some() {
+                return;
+            }
+            let result = if inner.header_written {
+                Ok(())
+            } else {
+                inner.header_written = true;
+                inner.sink.write_header(&names())
+            }
+            .and_then(|_| inner.sink.write_row(values));
+            if let Err(err) = result {
+                inner.error = Some(err);
+            }
+        }
+    }
+
+    /// Returns error, if any, that the attached sink has failed with
+    pub fn check(&self) -> anyhow::Result<()> {
+        match &self.inner {
+            Some(inner) => match &inner.lock().unwrap().error {
+                Some(err) => Err(anyhow::anyhow!("history sink failed: {:#}", err)),
+                None => Ok(()),
+            },
+            None => Ok(()),
+        }
+    }
+
+    /// Flushes attached sink, if any, or returns the error it has failed
+    /// with -- see [Self::check]
+    pub fn flush(&self) -> anyhow::Result<()> {
+        if let Some(inner) = &self.inner {
+            let mut inner = inner.lock().unwrap();
+            if inner.error.is_none() {
+                if let Err(err) = inner.sink.flush() {
+                    inner.error = Some(err);
+                }
+            }
+        }
+        self.check()
+    }
+}
+
+impl std::fmt::Debug for Sink {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Sink {{ attached: {}, keep_history: {} }}",
+            self.is_attached(),
+            self.keep_history
+        )
+    }
+}
+
+impl PartialEq for Sink {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl PartialOrd for Sink {
+    fn partial_cmp(&self, _other: &Self) -> Option<std::cmp::Ordering> {
+        Some(std::cmp::Ordering::Equal)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// sink that records what it is given and fails after `max_rows` rows
+    #[derive(Clone, Default)]
+    struct Recorder {
+        lines: Arc<Mutex<Vec<String>>>,
+        max_rows: usize,
+    }
+
+    impl HistorySink for Recorder {
+        fn write_header(&mut self, names: &[String]) -> anyhow::Result<()> {
+            self.lines.lock().unwrap().push(names.join(","));
+            Ok(())
+        }
+        fn write_row(&mut self, values: &[f64]) -> anyhow::Result<()> {
+            let mut lines = self.lines.lock().unwrap();
+            anyhow::ensure!(lines.len() <= self.max_rows, "No space left on device");
+            lines.push(format!("{:?}", values));
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_sink() {
+        let mut sink = Sink::default();
+        assert!(sink.keep_history());
+        // writing to a detached sink does nothing
+        sink.write(|| panic!("no header is needed"), &[0.0]);
+
+        let recorder = Recorder {
+            max_rows: 2,
+            ..Default::default()
+        };
+        sink.attach(recorder.clone(), false);
+        assert!(!sink.keep_history());
+        let clone = sink.clone();
+        assert!(clone.shares_with(&sink));
+        assert!(!Sink::default().shares_with(&Sink::default()));
+
+        for i in 0..4 {
+            clone.write(|| vec!["i".into(), "time".into()], &[i as f64, 0.5]);
+        }
+        // header is written once and rows after the first error are dropped
+        assert_eq!(
+            *recorder.lines.lock().unwrap(),
+            ["i,time", "[0.0, 0.5]", "[1.0, 0.5]"]
+        );
+        let err = sink.flush().unwrap_err();
+        assert!(err.to_string().contains("No space left on device"));
+        assert!(sink.detach().is_err());
+
+        let mut csv = CsvSink::new(Vec::new());
+        csv.write_header(&["i".into(), "time".into()]).unwrap();
+        csv.write_row(&[1.0, 0.25]).unwrap();
+        assert_eq!(String::from_utf8(csv.writer).unwrap(), "i,time\n1,0.25\n");
+    }
+}
@@ -0,0 +1,89 @@
+use crate::imports::*;
+use std::sync::Arc;
+
+/// Initial condition snapshot of a system, stored as bincode.
+/// Systems opt in with a field of this type marked `#[snapshot]`, which
+/// should also be marked `#[serde(skip)]`.  The snapshot is taken when a run
+/// starts from `state.i == 0` and is restored by the generated `reset`.
+/// The generated `checkpoint` stores it alongside the system, so `reset`
+/// still works after `resume`.
+/// Clones share the same snapshot, and the snapshot never affects equality
+/// or ordering of the system.
+#[derive(Clone, Default)]
+pub struct Snapshot(Option<Arc<Vec<u8>>>);
+
+impl Snapshot {
+    /// Returns snapshot of `value`
+    pub fn of<T: SerdeAPI>(value: &T) -> Self {
+        Self(Some(Arc::new(value.to_bincode())))
+    }
+
+    /// Returns true if a snapshot has been taken
+    pub fn is_taken(&self) -> bool {
+        self.0.is_some()
+    }
+
+    /// Discards snapshot so that the next run takes a new one
+    pub fn clear(&mut self) {
+        self.0 = None;
+    }
+
+    /// Returns encoded snapshot, if taken, e.g. to store it with a checkpoint
+    pub fn encoded(&self) -> Option<Vec<u8>> {
+        self.0.as_deref().cloned()
+    }
+
+    /// Returns snapshot from bytes returned by [Self::encoded]
+    pub fn from_encoded(encoded: Option<Vec<u8>>) -> Self {
+        Self(encoded.map(Arc::new))
+    }
+
+    /// Returns value stored by [Self::of]
+    pub fn restore<T: for<'a> Deserialize<'a>>(&self) -> anyhow::Result<T> {
+        match &self.0 {
+            Some(encoded) => Ok(bincode::deserialize(encoded)?),
+            None => Err(anyhow!("No snapshot has been taken")),
+        }
+    }
+}
+
+impl std::fmt::Debug for Snapshot {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Snapshot {{ taken: {} }}", self.is_taken())
+    }
+}
+
+impl PartialEq for Snapshot {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl PartialOrd for Snapshot {
+    fn partial_cmp(&self, _other: &Self) -> Option<std::cmp::Ordering> {
+        Some(std::cmp::Ordering::Equal)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SystemState;
+
+    #[test]
+    fn test_snapshot() {
+        let mut snapshot = Snapshot::default();
+        assert!(snapshot.restore::<SystemState>().is_err());
+        let state = SystemState { i: 3, time: 0.5 };
+        snapshot = Snapshot::of(&state);
+        assert!(snapshot.is_taken());
+        assert_eq!(snapshot.restore::<SystemState>().unwrap(), state);
+
+        let stored = Snapshot::from_encoded(snapshot.encoded());
+        assert_eq!(stored.restore::<SystemState>().unwrap(), state);
+        snapshot.clear();
+        assert!(!snapshot.is_taken());
+        // snapshots never affect equality of the systems they are part of
+        assert_eq!(snapshot, stored);
+    }
+}
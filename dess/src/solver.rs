@@ -1,4 +1,5 @@
 use crate::imports::*;
+use crate::time_series::{at_or_past, left_limit};
 
 #[common_derives]
 pub enum SolverTypes {
@@ -55,6 +56,7 @@ impl Default for SolverTypes {
                 ..Default::default()
             },
             history: Default::default(),
+            n_saved: Default::default(),
         }
     }
 
@@ -82,6 +84,9 @@ pub struct AdaptiveSolverConfig {
     pub state: SolverState,
     /// history of solver state
     pub history: SolverStateHistoryVec,
+    /// number of accepted time steps while `save` is true
+    #[serde(default)]
+    pub n_saved: usize,
 }
 
 impl Default for AdaptiveSolverConfig {
@@ -98,11 +103,23 @@ impl Default for AdaptiveSolverConfig {
                 ..Default::default()
             },
             history: Default::default(),
+            n_saved: Default::default(),
         }
     }
 }
 
 impl AdaptiveSolverConfig {
+    /// Pushes `state` to `history`, subject to `retention`
+    pub fn save_state(&mut self, retention: &Retention) {
+        if retention.keep(self.n_saved) {
+            self.history.push(self.state.clone());
+            if let Some(n) = retention.trim_to(self.history.len()) {
+                self.history.keep_last(n);
+            }
+        }
+        self.n_saved += 1;
+    }
+
     pub fn dt_mean(&self) -> Option<f64> {
         if !self.history.is_empty() {
             Some(self.history.dt.iter().fold(0., |acc, &x| acc + x) / self.history.len() as f64)
@@ -161,12 +178,123 @@ pub trait SolverBase: HasStates + Sized {
     fn update_derivs(&mut self);
     /// steps dt without affecting states
     fn step_time(&mut self, dt: &f64);
+    /// sets time `t` without affecting states
+    fn set_time(&mut self, t: f64);
     /// Returns `solver_conf`, if applicable
     fn sc(&self) -> Option<&AdaptiveSolverConfig>;
     /// Returns mut `solver_conf`, if applicable
     fn sc_mut(&mut self) -> Option<&mut AdaptiveSolverConfig>;
     /// Returns [Self::state]
     fn state(&self) -> &crate::SystemState;
+    /// Returns [SolverTypes] used by [Self::step_toward]
+    fn solver_type(&self) -> &SolverTypes;
+    /// Returns report times used by [Self::solve_step]
+    fn t_report(&self) -> &[f64];
+    /// Returns history [Retention] policy, if the system has a `#[retention]` field
+    fn retention(&self) -> Retention {
+        Retention::All
+    }
+    /// Returns first breakpoint after the current time of any field marked
+    /// `#[breakpoints]`.  Steps are shortened so as not to pass it.
+    fn next_breakpoint(&self) -> Option<f64> {
+        None
+    }
+    /// Moves time of a stage that has been stepped to breakpoint `t_b`,
+    /// taken at the start of the step, to just before it, so that inputs
+    /// that jump at `t_b` are evaluated at their left limit
+    fn to_left_limit(&mut self, t_b: Option<f64>) {
+        if let Some(t_b) = t_b {
+            if at_or_past(self.state().time, t_b) {
+                self.set_time(left_limit(t_b));
+            }
+        }
+    }
+    /// Runs `solver_type` specific steps until the next report time,
+    /// i.e. `t_report[0]` if no state has been saved yet and otherwise
+    /// the first value of `t_report` after the current time, so that
+    /// `walk` can continue after `advance_to`
+    fn solve_step(&mut self)
+    where
+        Self: SolverVariantMethods,
+    {
+        let time = self.state().time;
+        let t_next = if self.state().i == 0 {
+            self.t_report()[0]
+        } else {
+            *self
+                .t_report()
+                .iter()
+                .find(|t| **t > time)
+                .unwrap_or_else(|| self.t_report().last().unwrap())
+        };
+        while self.state().time < t_next {
+            self.step_toward(t_next);
+        }
+    }
+    /// Takes one `solver_type` specific step that does not go past `t_end`
+    /// or the next breakpoint, and notifies observers of it
+    fn step_toward(&mut self, t_end: f64)
+    where
+        Self: SolverVariantMethods,
+    {
+        let t_end = match self.next_breakpoint() {
+            Some(t_b) if t_b < t_end => t_b,
+            _ => t_end,
+        };
+        let dt = t_end - self.state().time;
+        let dt_used = match *self.solver_type() {
+            SolverTypes::EulerFixed { dt: dt_fixed } => {
+                let dt = dt.min(dt_fixed);
+                self.euler(&dt);
+                dt
+            }
+            SolverTypes::HeunsMethod { dt: dt_fixed } => {
+                let dt = dt.min(dt_fixed);
+                self.heun(&dt);
+                dt
+            }
+            SolverTypes::MidpointMethod { dt: dt_fixed } => {
+                let dt = dt.min(dt_fixed);
+                self.midpoint(&dt);
+                dt
+            }
+            SolverTypes::RalstonsMethod { dt: dt_fixed } => {
+                let dt = dt.min(dt_fixed);
+                self.ralston(&dt);
+                dt
+            }
+            SolverTypes::RK23BogackiShampine(_) => self.rk23_bogacki_shampine(&dt),
+            SolverTypes::RK4Fixed { dt: dt_fixed } => {
+                let dt = dt.min(dt_fixed);
+                self.rk4fixed(&dt);
+                dt
+            }
+            SolverTypes::RK45CashKarp(_) => self.rk45_cash_karp(&dt),
+        };
+        self.observe(true, dt_used);
+    }
+    /// Returns attached [Observers], if the system has an `#[observers]` field
+    fn observers(&self) -> Option<&Observers> {
+        None
+    }
+    /// Notifies attached observers of an accepted or rejected step of size `dt`
+    fn observe(&self, accepted: bool, dt: f64) {
+        if let Some(observers) = self.observers() {
+            if observers.is_empty() {
+                return;
+            }
+            let states = self.states();
+            let derivs = self.derivs();
+            observers.notify(&StepInfo {
+                time: self.state().time,
+                states: &states,
+                derivs: &derivs,
+                dt,
+                accepted,
+                solver_state: self.sc().map(|sc| &sc.state),
+            });
+        }
+    }
 }
 
 pub trait SolverVariantMethods: SolverBase {
@@ -180,6 +308,7 @@ pub trait SolverVariantMethods: SolverBase {
     /// See Heun's Method (the first listed Heun's method, not the one also known as Ralston's Method):
     /// https://en.wikipedia.org/wiki/Heun%27s_method
     fn heun(&mut self, dt: &f64) {
+        let t_b = self.next_breakpoint();
         self.update_derivs();
         //making copy without history, to avoid stepping dt twice
         let mut updated_self = self.bare_clone();
@@ -187,7 +316,10 @@ pub trait SolverVariantMethods: SolverBase {
         let deriv_0: Vec<f64> = self.derivs();
         //this will give euler's formula result
         self.step_states_by_dt(dt);
+        let time = self.state().time;
+        self.to_left_limit(t_b);
         self.update_derivs();
+        self.set_time(time);
         //recording derivative at endpoint of euler's method line
         let deriv_1: Vec<f64> = self.derivs();
         //creating new vector that is average of deriv_1 and deriv_2
@@ -251,6 +383,7 @@ pub trait SolverVariantMethods: SolverBase {
     ///solves time step with adaptive Bogacki Shampine Method (variant of RK23) and returns 'dt' used
     ///see: https://en.wikipedia.org/wiki/Bogacki%E2%80%93Shampine_method
     fn rk23_bogacki_shampine(&mut self, dt_max: &f64) -> f64 {
+        let retention = self.retention();
         let sc_mut = self.sc_mut().unwrap();
         // reset iteration counter
         sc_mut.state.n_iter = 0;
@@ -404,7 +537,7 @@ pub trait SolverVariantMethods: SolverBase {
             if break_cond {
                 // save before modifying dt
                 if sc_mut.save {
-                    sc_mut.history.push(sc_mut.state.clone());
+                    sc_mut.save_state(&retention);
                 }
                 // store used dt before adapting
                 let dt_used = sc_mut.state.dt;
@@ -414,6 +547,7 @@ pub trait SolverVariantMethods: SolverBase {
             };
             // adapt for next iteration in current time step
             sc_mut.state.dt *= dt_coeff;
+            self.observe(false, dt);
         };
 
         // increment forward with 3rd order solution
@@ -425,6 +559,7 @@ pub trait SolverVariantMethods: SolverBase {
         dt_used
     }
     fn rk23_bogacki_shampine_step(&mut self, dt: f64) -> (Vec<f64>, Vec<f64>) {
+        let t_b = self.next_breakpoint();
         self.update_derivs();
 
         // k1 = f(t_i, x_i)
@@ -456,6 +591,7 @@ pub trait SolverVariantMethods: SolverBase {
         };
         let delta3_new = delta3.clone();
         sys3.step_states(delta3_new);
+        sys3.to_left_limit(t_b);
         sys3.update_derivs();
         let k4s = sys3.derivs();
         // 2nd order delta
@@ -469,6 +605,7 @@ pub trait SolverVariantMethods: SolverBase {
     /// solves time step with 4th order Runge-Kutta method.
     /// See RK4 method: https://en.wikipedia.org/wiki/Runge%E2%80%93Kutta_methods#Examples
     fn rk4fixed(&mut self, dt: &f64) {
+        let t_b = self.next_breakpoint();
         self.update_derivs();
 
         // k1 = f(x_i, y_i)
@@ -491,6 +628,7 @@ pub trait SolverVariantMethods: SolverBase {
         let mut sys3 = self.bare_clone();
         sys3.set_derivs(&k3s);
         sys3.step_states_by_dt(dt);
+        sys3.to_left_limit(t_b);
         sys3.update_derivs();
         let k4s = sys3.derivs();
 
@@ -507,6 +645,7 @@ pub trait SolverVariantMethods: SolverBase {
     /// solves time step with adaptive Cash-Karp Method (variant of RK45) and returns `dt` used
     /// https://en.wikipedia.org/wiki/Cash%E2%80%93Karp_method
     fn rk45_cash_karp(&mut self, dt_max: &f64) -> f64 {
+        let retention = self.retention();
         let sc_mut = self.sc_mut().unwrap();
         // reset iteration counter
         sc_mut.state.n_iter = 0;
@@ -653,7 +792,7 @@ pub trait SolverVariantMethods: SolverBase {
             if break_cond {
                 // save before modifying dt
                 if sc_mut.save {
-                    sc_mut.history.push(sc_mut.state.clone());
+                    sc_mut.save_state(&retention);
                 }
                 // store used dt before adapting
                 let dt_used = sc_mut.state.dt;
@@ -663,6 +802,7 @@ pub trait SolverVariantMethods: SolverBase {
             };
             // adapt for next iteration in current time step
             sc_mut.state.dt *= dt_coeff;
+            self.observe(false, dt);
         };
 
         // increment forward with 5th order solution
@@ -675,6 +815,7 @@ pub trait SolverVariantMethods: SolverBase {
     }
 
     fn rk45_cash_karp_step(&mut self, dt: f64) -> (Vec<f64>, Vec<f64>) {
+        let t_b = self.next_breakpoint();
         self.update_derivs();
 
         // k1 = f(x_i, y_i)
@@ -725,6 +866,7 @@ pub trait SolverVariantMethods: SolverBase {
             }
             steps
         });
+        sys4.to_left_limit(t_b);
         sys4.update_derivs();
         let k5s = sys4.derivs();
 
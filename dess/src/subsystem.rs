@@ -0,0 +1,114 @@
+use crate::imports::*;
+use crate::solver::SolverBase;
+use crate::time_series::HasBreakpoints;
+use std::ops::{Deref, DerefMut};
+
+/// `#[solver]` system used as a component of another, e.g. a room in a
+/// building.  Fields of this type are marked `#[use_state]`, which flattens
+/// the states of the subsystem into those of the parent, and its nodes serve
+/// as ports that the parent connects to by path, e.g.
+/// `connect_states!(self, (room1.air, room2.air, partition))`.  Histories of
+/// the subsystem and its components are saved with those of the parent.
+///
+/// The parent's `update_derivs` must call [Self::update_derivs_at] before
+/// connecting to any ports, because the subsystem resets its own time
+/// derivatives.  The subsystem's `t_report`, `solver_type`, and `state.i`
+/// are not used.
+#[derive(Clone, Debug, Default, PartialEq, PartialOrd, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Subsystem<T>(pub T);
+
+impl<T: SolverBase> Subsystem<T> {
+    /// Sets time of subsystem to that of the parent, `t`, and updates time
+    /// derivatives of its states from its internal connections
+    pub fn update_derivs_at(&mut self, t: f64) {
+        self.0.set_time(t);
+        self.0.update_derivs();
+    }
+}
+
+impl<T> Deref for Subsystem<T> {
+    type Target = T;
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Subsystem<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+impl<T> From<T> for Subsystem<T> {
+    fn from(sys: T) -> Self {
+        Self(sys)
+    }
+}
+
+impl<T: BareClone> BareClone for Subsystem<T> {
+    fn bare_clone(&self) -> Self {
+        Self(self.0.bare_clone())
+    }
+}
+
+impl<T: SolverBase> HasStateSlice for Subsystem<T> {
+    fn state_labels(&self) -> Vec<String> {
+        self.0.state_names()
+    }
+    fn states(&self) -> Vec<f64> {
+        HasStates::states(&self.0)
+    }
+    fn set_states(&mut self, val: &[f64]) {
+        HasStates::set_states(&mut self.0, val.to_vec());
+    }
+    fn step_states_by_dt(&mut self, dt: &f64) {
+        // the parent keeps time, so unlike `HasStates::step_states_by_dt`
+        // this must not step the time of the subsystem
+        let val = HasStates::derivs(&self.0)
+            .iter()
+            .map(|deriv| dt * deriv)
+            .collect();
+        HasStates::step_states(&mut self.0, val);
+    }
+    fn step_states(&mut self, val: &[f64]) {
+        HasStates::step_states(&mut self.0, val.to_vec());
+    }
+    fn derivs(&self) -> Vec<f64> {
+        HasStates::derivs(&self.0)
+    }
+    fn set_derivs(&mut self, val: &[f64]) {
+        HasStates::set_derivs(&mut self.0, val);
+    }
+    fn step_derivs(&mut self, val: &[f64]) {
+        HasStates::step_derivs(&mut self.0, val.to_vec());
+    }
+    fn reset_derivs(&mut self) {
+        SolverBase::reset_derivs(&mut self.0);
+    }
+    fn storages(&self) -> Vec<f64> {
+        HasStates::storages(&self.0)
+    }
+}
+
+/// Breakpoints of the subsystem's own `#[breakpoints]` fields after the time
+/// last set by [Subsystem::update_derivs_at]
+impl<T: SolverBase> HasBreakpoints for Subsystem<T> {
+    fn next_breakpoint(&self, _t: f64) -> Option<f64> {
+        self.0.next_breakpoint()
+    }
+}
+
+#[cfg(feature = "pyo3")]
+impl<T: IntoPy<PyObject>> IntoPy<PyObject> for Subsystem<T> {
+    fn into_py(self, py: Python<'_>) -> PyObject {
+        self.0.into_py(py)
+    }
+}
+
+#[cfg(feature = "pyo3")]
+impl<'source, T: FromPyObject<'source>> FromPyObject<'source> for Subsystem<T> {
+    fn extract(ob: &'source PyAny) -> PyResult<Self> {
+        Ok(Self(T::extract(ob)?))
+    }
+}
@@ -0,0 +1,281 @@
+use crate::imports::*;
+use std::path::Path;
+
+/// Relative tolerance within which a time is considered to be at a breakpoint
+const BREAKPOINT_RTOL: f64 = 1e-12;
+
+/// Returns true if `t` has reached breakpoint `t_b`
+pub(crate) fn at_or_past(t: f64, t_b: f64) -> bool {
+    t_b - t <= BREAKPOINT_RTOL * t_b.abs().max(1.0)
+}
+
+/// Returns time just before breakpoint `t_b` that has not reached it, at
+/// which inputs that jump at `t_b` take their left limit
+pub(crate) fn left_limit(t_b: f64) -> f64 {
+    t_b - 2.0 * BREAKPOINT_RTOL * t_b.abs().max(1.0)
+}
+
+/// Returns `value` versus `time` interpolated at `t`, held constant outside
+/// of `time`, or `NAN` if there are no points
+pub(crate) fn interpolate(
+    time: &[f64],
+    value: &[f64],
+    interpolation: Interpolation,
+    t: f64,
+) -> f64 {
+    let n = time.len();
+    if n == 0 || n != value.len() {
+        return f64::NAN;
+    }
+    // number of times at or before `t`
+    let k = time.partition_point(|&t_k| at_or_past(t, t_k));
+    if k == 0 {
+        return value[0];
+    }
+    if k == n {
+        return value[n - 1];
+    }
+    let (t0, t1) = (time[k - 1], time[k]);
+    let (v0, v1) = (value[k - 1], value[k]);
+    let s = ((t - t0) / (t1 - t0)).clamp(0.0, 1.0);
+    match interpolation {
+        Interpolation::ZeroOrderHold => v0,
+        Interpolation::Linear => v0 + s * (v1 - v0),
+        Interpolation::Cubic => {
+            // finite-difference slope at point `k`, scaled to the interval
+            let slope = |k: usize| {
+                let (lo, hi) = (k.saturating_sub(1), (k + 1).min(n - 1));
+                (value[hi] - value[lo]) / (time[hi] - time[lo]) * (t1 - t0)
+            };
+            let (m0, m1) = (slope(k - 1), slope(k));
+            let (s2, s3) = (s * s, s * s * s);
+            (2. * s3 - 3. * s2 + 1.) * v0
+                + (s3 - 2. * s2 + s) * m0
+                + (-2. * s3 + 3. * s2) * v1
+                + (s3 - s2) * m1
+        }
+    }
+}
+
+/// Checks that `time` has at least one point, `value` has the same length,
+/// `time` is strictly increasing, and all points are finite.  `table` names
+/// the table in errors, e.g. `Time series`.
+pub(crate) fn validate_points(table: &str, time: &[f64], value: &[f64]) -> anyhow::Result<()> {
+    if time.is_empty() {
+        return Err(anyhow!("{} has no points", table));
+    }
+    if time.len() != value.len() {
+        return Err(anyhow!(
+            "{} has {} times but {} values",
+            table,
+            time.len(),
+            value.len()
+        ));
+    }
+    if let Some(k) = (0..time.len()).find(|&k| !time[k].is_finite() || !value[k].is_finite()) {
+        return Err(anyhow!(
+            "{} point {} at time {} with value {} is not finite",
+            table,
+            k,
+            time[k],
+            value[k]
+        ));
+    }
+    if let Some(k) = time.windows(2).position(|w| w[1] <= w[0]) {
+        return Err(anyhow!(
+            "{} times must be strictly increasing, but time[{}] = {} is followed by {}",
+            table,
+            k,
+            time[k],
+            time[k + 1]
+        ));
+    }
+    Ok(())
+}
+
+/// Component whose value changes discontinuously, or whose derivative
+/// does, at known times.  Fields of a `solver` system marked
+/// `#[breakpoints]` must implement this, and the solver then shortens
+/// steps so that they end exactly on each breakpoint.
+pub trait HasBreakpoints {
+    /// Returns first breakpoint after time `t`, if any
+    fn next_breakpoint(&self, t: f64) -> Option<f64>;
+}
+
+impl<T: HasBreakpoints> HasBreakpoints for Option<T> {
+    fn next_breakpoint(&self, t: f64) -> Option<f64> {
+        self.as_ref().and_then(|x| x.next_breakpoint(t))
+    }
+}
+
+/// Interpolation between points of a [TimeSeriesInput]
+#[derive(Default, Copy)]
+#[common_derives]
+pub enum Interpolation {
+    /// holds each value until the next time
+    ZeroOrderHold,
+    /// straight line between neighboring points
+    #[default]
+    Linear,
+    /// cubic Hermite spline with finite-difference slopes, which passes
+    /// through every point and has a continuous first derivative
+    Cubic,
+}
+
+/// Lookup table of `value` versus `time` that is interpolated at the
+/// current time of a system, e.g. to set the temperature of a reservoir
+/// inside `update_derivs`.  Values are held constant before the first
+/// and after the last time.  Can be read from a two column CSV file with
+/// [Self::from_csv] or from YAML, e.g.
+/// ```yaml
+/// time: [0.0, 0.5, 1.0]
+/// value: [10.0, 12.0, 11.0]
+/// interpolation: ZeroOrderHold
+/// ```
+#[derive(Default)]
+#[common_derives]
+pub struct TimeSeriesInput {
+    /// strictly increasing times \[s\]
+    pub time: Vec<f64>,
+    /// value at each of `time`
+    pub value: Vec<f64>,
+    #[serde(default)]
+    pub interpolation: Interpolation,
+}
+
+impl TimeSeriesInput {
+    /// Returns new table, failing if `time` and `value` are not valid
+    pub fn new(
+        time: Vec<f64>,
+        value: Vec<f64>,
+        interpolation: Interpolation,
+    ) -> anyhow::Result<Self> {
+        let input = Self {
+            time,
+            value,
+            interpolation,
+        };
+        input.validate()?;
+        Ok(input)
+    }
+
+    /// Reads table from CSV file with time in the first column and value
+    /// in the second.  Blank lines, lines starting with `#`, and the first
+    /// remaining row, if it is not numeric, i.e. a header, are skipped.
+    /// Any other row that is not numeric is an error.
+    pub fn from_csv<P: AsRef<Path>>(path: P, interpolation: Interpolation) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path)?;
+        let mut time = Vec::new();
+        let mut value = Vec::new();
+        let mut first_row = true;
+        for (line_num, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let is_first_row = std::mem::replace(&mut first_row, false);
+            let cells: Vec<&str> = line.split(',').map(str::trim).collect();
+            let parsed = match cells.as_slice() {
+                [t, v, ..] => t.parse::<f64>().and_then(|t| Ok((t, v.parse::<f64>()?))),
+                _ => {
+                    return Err(anyhow!(
+                        "{}:{}: expected at least 2 columns",
+                        path.display(),
+                        line_num + 1
+                    ))
+                }
+            };
+            match parsed {
+                Ok((t, v)) => {
+                    time.push(t);
+                    value.push(v);
+                }
+                Err(_) if is_first_row => continue,
+                Err(err) => return Err(anyhow!("{}:{}: {}", path.display(), line_num + 1, err)),
+            }
+        }
+        Self::new(time, value, interpolation)
+    }
+
+    /// Checks that there is at least one point, `time` and `value` have the
+    /// same length, `time` is strictly increasing, and all points are finite
+    pub fn validate(&self) -> anyhow::Result<()> {
+        validate_points("Time series", &self.time, &self.value)
+    }
+
+    /// Returns interpolated value at time `t`, or `NAN` if there are no points
+    pub fn value_at(&self, t: f64) -> f64 {
+        interpolate(&self.time, &self.value, self.interpolation, t)
+    }
+
+    /// Returns times at which the value or its derivative is discontinuous
+    pub fn breakpoints(&self) -> Vec<f64> {
+        match self.interpolation {
+            Interpolation::ZeroOrderHold | Interpolation::Linear => self.time.clone(),
+            // interior points are smooth, but the ends switch to holding
+            Interpolation::Cubic => {
+                let mut ends: Vec<f64> = self.time.first().copied().into_iter().collect();
+                if self.time.len() > 1 {
+                    ends.extend(self.time.last());
+                }
+                ends
+            }
+        }
+    }
+}
+
+impl HasBreakpoints for TimeSeriesInput {
+    fn next_breakpoint(&self, t: f64) -> Option<f64> {
+        self.breakpoints()
+            .into_iter()
+            .find(|&t_b| !at_or_past(t, t_b))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn input(interpolation: Interpolation) -> TimeSeriesInput {
+        TimeSeriesInput::new(vec![0.0, 0.5, 1.0], vec![10.0, 12.0, 11.0], interpolation).unwrap()
+    }
+
+    #[test]
+    fn test_interpolation() {
+        let zoh = input(Interpolation::ZeroOrderHold);
+        let linear = input(Interpolation::Linear);
+        let cubic = input(Interpolation::Cubic);
+        for series in [&zoh, &linear, &cubic] {
+            assert_eq!(series.value_at(-1.0), 10.0);
+            assert_eq!(series.value_at(0.5), 12.0);
+            assert_eq!(series.value_at(2.0), 11.0);
+        }
+        assert_eq!(zoh.value_at(0.49), 10.0);
+        assert_eq!(linear.value_at(0.25), 11.0);
+        assert!(cubic.value_at(0.25) > 11.0 && cubic.value_at(0.25) < 12.0);
+        assert_eq!(linear.next_breakpoint(0.0), Some(0.5));
+        assert_eq!(cubic.next_breakpoint(0.0), Some(1.0));
+        assert!(TimeSeriesInput::new(vec![0.0, 0.0], vec![1.0, 2.0], Default::default()).is_err());
+    }
+
+    #[test]
+    fn test_from_csv_and_yaml() {
+        let linear = input(Interpolation::Linear);
+        let csv_file = std::env::temp_dir().join("dess_test_time_series_input.csv");
+        std::fs::write(&csv_file, "time,value\n0.0,10.0\n0.5,12.0\n\n1.0,11.0\n").unwrap();
+        let from_csv = TimeSeriesInput::from_csv(&csv_file, Interpolation::Linear).unwrap();
+        assert_eq!(from_csv, linear);
+        // only a single leading header row is skipped
+        std::fs::write(&csv_file, "time,value\nsecond,kelvin\n0.0,10.0\n").unwrap();
+        let err = TimeSeriesInput::from_csv(&csv_file, Interpolation::Linear).unwrap_err();
+        assert!(err.to_string().contains(".csv:2: "), "{}", err);
+        std::fs::write(&csv_file, "0.0,10.0\n0.5,n/a\n").unwrap();
+        assert!(TimeSeriesInput::from_csv(&csv_file, Interpolation::Linear).is_err());
+        std::fs::remove_file(&csv_file).unwrap();
+        let from_yaml =
+            TimeSeriesInput::from_yaml("{time: [0.0, 0.5, 1.0], value: [10.0, 12.0, 11.0]}")
+                .unwrap();
+        assert_eq!(from_yaml, linear);
+    }
+}
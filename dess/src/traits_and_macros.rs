@@ -26,23 +26,92 @@ macro_rules! zip {
 }
 
 /// assumes heat flow from source -> sink is positive
-/// sets flow variable values
+/// sets flow variable values, or for [HasFlowState] components, the time
+/// derivatives of flow variables.  A [Source] attached to a single node is
+/// given as a pair, e.g. `(m1, q1)`, and sets its flow at `$sys.state.time`.
+/// Elements of `Vec` or array fields are given by index, e.g.
+/// `(m0, nodes[0], h0)`, and `[nodes, links]` chains `links[i]` between
+/// `nodes[i]` and `nodes[i + 1]` for every element of `links`, so `nodes`
+/// must have exactly one more element than `links`.  Nodes of a
+/// [Subsystem](crate::subsystem::Subsystem) are given by path, e.g.
+/// `(room1.air, room2.air, partition)`.
 #[macro_export]
 macro_rules! connect_states {
-    ($sys: ident, $(($s0: ident, $s1: ident, $c: ident)), +) => {
+    (@connect $sys: ident, [$nodes: ident, $conns: ident]) => {
+        debug_assert_eq!(
+            $sys.$conns.len() + 1,
+            $sys.$nodes.len(),
+            concat!("`[", stringify!($nodes), ", ", stringify!($conns), "]` needs one more node than links"),
+        );
+        for i in 0..$sys.$conns.len() {
+            $sys.$conns[i].set_flow(&$sys.$nodes[i], &$sys.$nodes[i + 1]);
+        }
+    };
+    (@connect $sys: ident, (
+        $s0: ident $(.$p0: ident)* $([$i0: expr])?,
+        $s1: ident $(.$p1: ident)* $([$i1: expr])?,
+        $c: ident $(.$pc: ident)* $([$ic: expr])?
+    )) => {
+        $sys.$c$(.$pc)*$([$ic])?.set_flow(
+            &$sys.$s0$(.$p0)*$([$i0])?,
+            &$sys.$s1$(.$p1)*$([$i1])?,
+        );
+    };
+    (@connect $sys: ident, (
+        $s: ident $(.$p: ident)* $([$i: expr])?,
+        $c: ident $(.$pc: ident)* $([$ic: expr])?
+    )) => {
+        $sys.$c$(.$pc)*$([$ic])?.set_source_flow(&$sys.$s$(.$p)*$([$i])?, $sys.state.time);
+    };
+    ($sys: ident, $($conn: tt), +) => {
         $(
-            $sys.$c.set_flow(&$sys.$s0, &$sys.$s1);
+            $crate::connect_states!(@connect $sys, $conn);
         )+
     };
 }
 
-/// sets time derivatives of state variables based on connected flow variables
+/// sets time derivatives of state variables based on connected flow variables,
+/// which may be algebraic ([Flow]) or states themselves ([HasFlowState]).
+/// Flow from a [Source], given as a pair, e.g. `(m1, q1)`, goes into its node.
+/// Takes the same forms as [connect_states].
 #[macro_export]
 macro_rules! update_derivs {
-    ($sys: ident, $(($s0: ident, $s1: ident, $c: ident)), +) => {
+    (@update $sys: ident, [$nodes: ident, $conns: ident]) => {
+        debug_assert_eq!(
+            $sys.$conns.len() + 1,
+            $sys.$nodes.len(),
+            concat!("`[", stringify!($nodes), ", ", stringify!($conns), "]` needs one more node than links"),
+        );
+        for i in 0..$sys.$conns.len() {
+            let flow = $sys.$conns[i].flow();
+            let storage = $sys.$nodes[i].storage();
+            $sys.$nodes[i].step_deriv(-flow / storage);
+            let storage = $sys.$nodes[i + 1].storage();
+            $sys.$nodes[i + 1].step_deriv(flow / storage);
+        }
+    };
+    (@update $sys: ident, (
+        $s0: ident $(.$p0: ident)* $([$i0: expr])?,
+        $s1: ident $(.$p1: ident)* $([$i1: expr])?,
+        $c: ident $(.$pc: ident)* $([$ic: expr])?
+    )) => {{
+        let flow = $sys.$c$(.$pc)*$([$ic])?.flow();
+        let storage = $sys.$s0$(.$p0)*$([$i0])?.storage();
+        $sys.$s0$(.$p0)*$([$i0])?.step_deriv(-flow / storage);
+        let storage = $sys.$s1$(.$p1)*$([$i1])?.storage();
+        $sys.$s1$(.$p1)*$([$i1])?.step_deriv(flow / storage);
+    }};
+    (@update $sys: ident, (
+        $s: ident $(.$p: ident)* $([$i: expr])?,
+        $c: ident $(.$pc: ident)* $([$ic: expr])?
+    )) => {{
+        let flow = $sys.$c$(.$pc)*$([$ic])?.flow();
+        let storage = $sys.$s$(.$p)*$([$i])?.storage();
+        $sys.$s$(.$p)*$([$i])?.step_deriv(flow / storage);
+    }};
+    ($sys: ident, $($conn: tt), +) => {
         $(
-            $sys.$s0.step_deriv(-$sys.$c.flow() / $sys.$s0.storage());
-            $sys.$s1.step_deriv($sys.$c.flow() / $sys.$s1.storage());
+            $crate::update_derivs!(@update $sys, $conn);
         )+
     };
 }
@@ -106,6 +175,179 @@ pub trait HasState {
     fn storage(&self) -> f64;
 }
 
+/// Component with any number of potential variables, e.g. a battery cell
+/// with state of charge and temperature or a wall discretized into layers,
+/// whose states occupy a contiguous slice of the system state vector.
+/// Every [HasState] is a slice of one, so fields of either kind are marked
+/// `#[use_state]`.  Methods taking a slice expect [Self::n_states] values.
+pub trait HasStateSlice {
+    /// returns number of states
+    fn n_states(&self) -> usize {
+        self.states().len()
+    }
+    /// returns labels distinguishing states, which are appended to the
+    /// field name in e.g. `state_names`, or a single empty label for a
+    /// component with one state
+    fn state_labels(&self) -> Vec<String> {
+        (0..self.n_states()).map(|i| i.to_string()).collect()
+    }
+    /// returns values of states
+    fn states(&self) -> Vec<f64>;
+    /// sets values of states
+    fn set_states(&mut self, val: &[f64]);
+    /// increments values of states by `dt * self.derivs()`
+    fn step_states_by_dt(&mut self, dt: &f64) {
+        let val: Vec<f64> = self.derivs().iter().map(|deriv| dt * deriv).collect();
+        self.step_states(&val);
+    }
+    /// increments values of states by `val`
+    fn step_states(&mut self, val: &[f64]) {
+        let states: Vec<f64> = self
+            .states()
+            .iter()
+            .zip(val)
+            .map(|(x, dx)| x + dx)
+            .collect();
+        self.set_states(&states);
+    }
+    /// returns time derivatives of states
+    fn derivs(&self) -> Vec<f64>;
+    /// sets values of time derivatives of states
+    fn set_derivs(&mut self, val: &[f64]);
+    /// increments values of time derivatives of states by `val`
+    fn step_derivs(&mut self, val: &[f64]) {
+        let derivs: Vec<f64> = self
+            .derivs()
+            .iter()
+            .zip(val)
+            .map(|(x, dx)| x + dx)
+            .collect();
+        self.set_derivs(&derivs);
+    }
+    /// sets time derivatives of states to zero
+    fn reset_derivs(&mut self) {
+        self.set_derivs(&vec![0.0; self.n_states()]);
+    }
+    /// returns values of storage variables (e.g. thermal capacitance \[J/K\])
+    fn storages(&self) -> Vec<f64>;
+}
+
+/// Removes and returns first `n` values of `val`, e.g. the slice belonging
+/// to one [HasStateSlice] in a system state vector
+pub fn split_states<'a>(val: &mut &'a [f64], n: usize) -> &'a [f64] {
+    let (head, tail) = val.split_at(n);
+    *val = tail;
+    head
+}
+
+impl<T: HasState> HasStateSlice for T {
+    fn n_states(&self) -> usize {
+        1
+    }
+    fn state_labels(&self) -> Vec<String> {
+        vec![String::new()]
+    }
+    fn states(&self) -> Vec<f64> {
+        vec![self.state()]
+    }
+    fn set_states(&mut self, val: &[f64]) {
+        self.set_state(val[0]);
+    }
+    fn step_states_by_dt(&mut self, dt: &f64) {
+        self.step_state_by_dt(dt);
+    }
+    fn step_states(&mut self, val: &[f64]) {
+        self.step_state(val[0]);
+    }
+    fn derivs(&self) -> Vec<f64> {
+        vec![self.deriv()]
+    }
+    fn set_derivs(&mut self, val: &[f64]) {
+        self.set_deriv(val[0]);
+    }
+    fn step_derivs(&mut self, val: &[f64]) {
+        self.step_deriv(val[0]);
+    }
+    fn reset_derivs(&mut self) {
+        self.set_deriv(0.0);
+    }
+    fn storages(&self) -> Vec<f64> {
+        vec![self.storage()]
+    }
+}
+
+/// Methods of [HasStateSlice] for a collection of [HasStateSlice]s, e.g. a
+/// `Vec` of nodes in a discretized wall, whose states are concatenated in
+/// order and labeled by index
+macro_rules! state_slice_collection_methods {
+    () => {
+        fn n_states(&self) -> usize {
+            self.iter().map(|x| x.n_states()).sum()
+        }
+        fn state_labels(&self) -> Vec<String> {
+            self.iter()
+                .enumerate()
+                .flat_map(|(i, x)| {
+                    x.state_labels().into_iter().map(move |label| {
+                        if label.is_empty() {
+                            i.to_string()
+                        } else {
+                            format!("{}.{}", i, label)
+                        }
+                    })
+                })
+                .collect()
+        }
+        fn states(&self) -> Vec<f64> {
+            self.iter().flat_map(|x| x.states()).collect()
+        }
+        fn set_states(&mut self, mut val: &[f64]) {
+            for x in self.iter_mut() {
+                let n = x.n_states();
+                x.set_states(split_states(&mut val, n));
+            }
+        }
+        fn step_states_by_dt(&mut self, dt: &f64) {
+            self.iter_mut().for_each(|x| x.step_states_by_dt(dt));
+        }
+        fn step_states(&mut self, mut val: &[f64]) {
+            for x in self.iter_mut() {
+                let n = x.n_states();
+                x.step_states(split_states(&mut val, n));
+            }
+        }
+        fn derivs(&self) -> Vec<f64> {
+            self.iter().flat_map(|x| x.derivs()).collect()
+        }
+        fn set_derivs(&mut self, mut val: &[f64]) {
+            for x in self.iter_mut() {
+                let n = x.n_states();
+                x.set_derivs(split_states(&mut val, n));
+            }
+        }
+        fn step_derivs(&mut self, mut val: &[f64]) {
+            for x in self.iter_mut() {
+                let n = x.n_states();
+                x.step_derivs(split_states(&mut val, n));
+            }
+        }
+        fn reset_derivs(&mut self) {
+            self.iter_mut().for_each(|x| x.reset_derivs());
+        }
+        fn storages(&self) -> Vec<f64> {
+            self.iter().flat_map(|x| x.storages()).collect()
+        }
+    };
+}
+
+impl<T: HasStateSlice> HasStateSlice for Vec<T> {
+    state_slice_collection_methods!();
+}
+
+impl<T: HasStateSlice, const N: usize> HasStateSlice for [T; N] {
+    state_slice_collection_methods!();
+}
+
 pub trait HasStates: BareClone {
     /// returns values of states
     fn states(&self) -> Vec<f64>;
@@ -125,6 +367,8 @@ pub trait HasStates: BareClone {
     fn step_derivs(&mut self, val: Vec<f64>);
     /// returns value of storage variable (e.g. thermal capacitance \[J/K\])
     fn storages(&self) -> Vec<f64>;
+    /// returns names of states in the order of [Self::states]
+    fn state_names(&self) -> Vec<String>;
 }
 
 pub trait Flow {
@@ -134,6 +378,46 @@ pub trait Flow {
     fn flow(&self) -> f64;
 }
 
+/// Component whose flow variable is itself a state, e.g. inductor current
+/// or fluid flow through an inertance, with a time derivative driven by the
+/// difference between the two potentials it connects.  Its [Flow::set_flow]
+/// sets that time derivative rather than the flow, and fields of this type
+/// are marked `#[use_flow_state]` so that the `solver` macro integrates them.
+pub trait HasFlowState: Flow {
+    /// sets value `val` of flow variable
+    fn set_flow_state(&mut self, val: f64);
+    /// increments value of flow variable by multiplying `dt * self.flow_deriv()`
+    /// and adding to previous value
+    fn step_flow_state_by_dt(&mut self, dt: &f64) {
+        self.set_flow_state(self.flow() + dt * self.flow_deriv());
+    }
+    /// increments value of flow variable by `val`
+    fn step_flow_state(&mut self, val: f64) {
+        self.set_flow_state(self.flow() + val);
+    }
+    /// returns value of time derivative of flow variable
+    fn flow_deriv(&self) -> f64;
+    /// sets value `val` of time derivative of flow variable
+    fn set_flow_deriv(&mut self, val: f64);
+    /// increments value of time derivative of flow variable
+    fn step_flow_deriv(&mut self, val: f64) {
+        self.set_flow_deriv(self.flow_deriv() + val)
+    }
+    /// returns value of inertance (e.g. inductance \[H\]), by which the
+    /// potential difference is divided to get the time derivative of flow
+    fn inertance(&self) -> f64;
+}
+
+/// Component attached to a single node that injects a flow into it, e.g. a
+/// heat rate or current, which may depend on time and on the node's
+/// potential but not on any other node
+pub trait Source {
+    /// Sets flow variable into node `p` at time `t`
+    fn set_source_flow(&mut self, p: &dyn HasState, t: f64);
+    /// returns value of flow variable into the node
+    fn flow(&self) -> f64;
+}
+
 pub trait Diff {
     fn diff(&self) -> Vec<f64>;
 }
@@ -157,7 +441,7 @@ pub trait SerdeAPI: Serialize + for<'a> Deserialize<'a> {
     /// # Argument:
     ///
     /// * `filename`: a `str` storing the targeted file name. Currently `.json` and `.yaml` suffixes are
-    /// supported
+    ///   supported
     ///
     /// # Returns:
     ///
@@ -188,7 +472,7 @@ pub trait SerdeAPI: Serialize + for<'a> Deserialize<'a> {
     /// # Argument:
     ///
     /// * `filename`: a `str` storing the targeted file name. Currently `.json` and `.yaml` suffixes are
-    /// supported
+    ///   supported
     ///
     /// # Returns:
     ///
@@ -264,3 +548,12 @@ impl Linspace for Vec<f64> {}
 pub trait BareClone {
     fn bare_clone(&self) -> Self;
 }
+
+/// Flat view of the numeric fields of a state struct, generated by `HistoryVec` derive.
+/// Non-numeric fields (e.g. `Vec`) are omitted, and `None` values become `f64::NAN`.
+pub trait StateColumns {
+    /// returns names of numeric fields
+    fn column_names() -> Vec<String>;
+    /// returns values of numeric fields in same order as [Self::column_names]
+    fn column_values(&self) -> Vec<f64>;
+}